@@ -1,6 +1,8 @@
+use crate::pts_loader::dataset::{broadcast_day, local_time, utc_time};
 use crate::pts_loader::sistandard::starttime_from_str;
-use chrono::{DateTime, NaiveDate, Utc, Days};
-use clap::{CommandFactory, Parser};
+use crate::utils::messages::{Lang, Msg};
+use chrono::{DateTime, Days, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::fs::File;
@@ -17,6 +19,68 @@ pub struct Range {
     pub end_time: DateTime<Utc>,
 }
 
+impl Range {
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        self.start_time <= time && time <= self.end_time
+    }
+}
+
+// "today 18:00", "tomorrow", "2024-05-01T20:00" or "01.05.2024 20:00" in
+// local time
+fn parse_point(text: &str, utc: bool) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    let (date, time) = match text.split_once(' ').or_else(|| {
+        text.split_once('T')
+            .filter(|(date, _)| date.ends_with(|c: char| c.is_ascii_digit()))
+    }) {
+        Some((date, time)) => (date, Some(time.trim())),
+        None => (text, None),
+    };
+    let today = local_time(Utc::now(), utc).date();
+    let date = match date.to_lowercase().as_str() {
+        "today" | "heute" => today,
+        "tomorrow" | "morgen" => today.succ_opt()?,
+        "yesterday" | "gestern" => today.pred_opt()?,
+        date => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(date, "%d.%m.%Y"))
+            .ok()?,
+    };
+    let time = match time {
+        None => NaiveTime::MIN,
+        Some(time) => NaiveTime::parse_from_str(time, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+            .ok()?,
+    };
+    Some(date.and_time(time))
+}
+
+// "+6h", "+90m" or "+1d"
+fn parse_offset(text: &str) -> Option<Duration> {
+    let text = text.trim().strip_prefix('+')?;
+    let (number, unit) = text.split_at(text.len().checked_sub(1)?);
+    let number: i64 = number.parse().ok()?;
+    match unit {
+        "d" => Duration::try_days(number),
+        "h" => Duration::try_hours(number),
+        "m" => Duration::try_minutes(number),
+        _ => None,
+    }
+}
+
+// "today 18:00..+6h" or "2024-05-01T20:00..2024-05-01T23:00"
+fn friendly_range(text: &str, utc: bool) -> Option<Range> {
+    let (from, to) = text.split_once("..")?;
+    let start = parse_point(from, utc)?;
+    let end = match parse_offset(to) {
+        Some(offset) => start + offset,
+        None => parse_point(to, utc)?,
+    };
+    Some(Range {
+        start_time: utc_time(start, utc),
+        end_time: utc_time(end, utc),
+    })
+}
+
 impl std::fmt::Display for Range {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({}, {})", self.start_time, self.end_time)
@@ -55,98 +119,281 @@ const DEFAULT_VALID_RANGE: &str = "DEFAULT_VALID_RANGE";
 const DEFAULT_FLUID_DATABASE: &str =
     "C:\\Users\\SimonGraetz\\OneDrive - CreateCtrl AG\\uhd1-plannung\\uhd_fluid_database.csv";
 
+// --lang takes "de", "en" or a locale like "de_DE.UTF-8"
+fn lang_value(s: &str) -> Result<String, String> {
+    match Lang::parse(s) {
+        Some(..) => Ok(s.to_string()),
+        None => Err(Msg::UnknownLang.fill(&[&format!("{:?}", s)])),
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Subcommand, Debug)]
+enum Command {
+    /// compare the schedule with other sources
+    Check {
+        /// planning workbook with the booked commercials per break
+        #[arg(long)]
+        plan: Option<String>,
+
+        /// worksheet of the planning workbook, default is the first one
+        #[arg(long)]
+        sheet: Option<String>,
+    },
+    /// correct the schedule and write a new pts file
+    Fix {
+        #[command(subcommand)]
+        target: FixTarget,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize, Subcommand, Debug)]
+enum FixTarget {
+    /// close the gaps and overlaps between siEvents
+    Si {
+        /// how a gap or an overlap is closed
+        #[arg(long, value_enum, default_value_t = FixStrategy::ExtendPrevious)]
+        strategy: FixStrategy,
+
+        /// the corrected file, default is <file>_fixed.pts next to the file
+        #[arg(long)]
+        output: Option<String>,
+
+        /// only show the changes
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum, Debug)]
+pub enum FixStrategy {
+    /// the previous siEvent ends where the next one starts
+    ExtendPrevious,
+    /// the next siEvent starts where the previous one ends
+    PullNext,
+    /// both meet at the nearest start or end of a vaEvent
+    SnapVa,
+}
+
 #[derive(Clone, Serialize, Deserialize, Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short, long, default_value_t = String::from("YOU_PICK_A_FILE"))]
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[arg(short, long, global = true, default_value_t = String::from("YOU_PICK_A_FILE"))]
     filename: String,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     repl: bool,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     verbose: bool,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     ps_event: bool,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     utc: bool,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     sierrors: bool,
 
-    #[arg(short, long, default_value_t = String::from("YOU_PICK_ILLEGAL_EVENTS"))]
+    #[arg(short, long, global = true, default_value_t = String::from("YOU_PICK_ILLEGAL_EVENTS"))]
     illegalevents: String,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     all: bool,
 
-    #[arg(long, default_value_t = String::from(""))]
+    /// deprecated, use `check --plan`
+    #[arg(long, global = true, default_value_t = String::from(""))]
     werbungen: String,
 
-    #[arg(long, default_value_t = String::from(""))]
+    #[arg(long, global = true, default_value_t = String::from(""))]
     tcins_and_tcouts: String,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     only_errors: bool,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     csv: Option<Option<String>>,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     xlsx: Option<Option<String>>,
 
-    #[arg(short, long, default_value_t = String::from("utf-8"))]
+    #[arg(short, long, global = true, default_value_t = String::from("utf-8"))]
     encoding: String,
 
-    #[arg(long, default_value_t = -1)]
+    #[arg(long, global = true, default_value_t = -1)]
     fps: i64,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     vaerrors: bool,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     missing_texts: bool,
 
-    #[arg(long, default_value_t = String::from(DEFAULT_VALID_RANGE))]
+    #[arg(long, global = true, default_value_t = String::from(DEFAULT_VALID_RANGE))]
     valid_range: String,
 
-    #[arg(short, long, default_value_t = false)]
+    #[arg(short, long, global = true, default_value_t = false)]
     debug: bool,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     today: Option<Option<String>>,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     day: Option<String>,
 
-    #[arg(long, default_value_t = 5 * 60 * 1000)]
+    #[arg(long, global = true, default_value_t = 5 * 60 * 1000)]
     minimum: i64,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     update_werbungen: bool,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     fluid: Option<Option<String>>,
 
-    #[arg(long)]
+    #[arg(long, global = true)]
     content_ids_to_ignore: Option<String>,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     display_sievents: bool,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     display_trailers: bool,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     only_sendepausen: bool,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     check_all_contentids: bool,
 
-    #[arg(long, default_value_t = false)]
+    #[arg(long, global = true, default_value_t = false)]
     update_fluid_data_base: bool,
+
+    #[arg(long, global = true)]
+    columns: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    no_color: bool,
+
+    #[arg(long, global = true, default_value_t = false)]
+    ascii: bool,
+
+    #[arg(long, global = true)]
+    width: Option<usize>,
+
+    #[arg(long, global = true, value_parser = lang_value)]
+    lang: Option<String>,
+
+    #[arg(long, global = true)]
+    input_encoding: Option<String>,
+
+    #[arg(long, global = true)]
+    entries: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    logo_coverage: bool,
+
+    #[arg(long, global = true, default_value_t = false)]
+    layout_conflicts: bool,
+
+    #[arg(long, global = true)]
+    layout_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    compliance: bool,
+
+    #[arg(long, global = true)]
+    compliance_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    advertising_time: bool,
+
+    #[arg(long, global = true, default_value_t = 12 * 60)]
+    ad_limit: i64,
+
+    #[arg(long, global = true, default_value_t = 60)]
+    ad_window: i64,
+
+    #[arg(long, global = true, default_value_t = String::from(""))]
+    ad_content_ids: String,
+
+    #[arg(long, global = true, default_value_t = false)]
+    interruptions: bool,
+
+    #[arg(long, global = true, default_value_t = 20)]
+    min_segment: i64,
+
+    #[arg(long, global = true, default_value_t = 30)]
+    break_interval: i64,
+
+    #[arg(long, global = true, default_value_t = false)]
+    youth_protection: bool,
+
+    #[arg(long, global = true)]
+    youth_rules: Option<String>,
+
+    #[arg(long, global = true)]
+    ratings: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    identifiers: bool,
+
+    #[arg(long, global = true)]
+    programid_pattern: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    reruns: bool,
+
+    #[arg(long, global = true)]
+    rerun_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    day_coverage: bool,
+
+    #[arg(long, global = true, default_value_t = 0)]
+    day_start: u32,
+
+    #[arg(long, global = true, default_value_t = false)]
+    sendepause: bool,
+
+    #[arg(long, global = true)]
+    sendepause_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    material_bounds: bool,
+
+    #[arg(long, global = true, default_value_t = false)]
+    material_status: bool,
+
+    #[arg(long, global = true, default_value_t = false)]
+    video_format: bool,
+
+    #[arg(long, global = true)]
+    format_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    trailer_balls: bool,
+
+    #[arg(long, global = true)]
+    ball_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    epg_text: bool,
+
+    #[arg(long, global = true)]
+    epg_rules: Option<String>,
+
+    #[arg(long, global = true, default_value_t = false)]
+    titles: bool,
+
+    // share of the words of the shorter title found in the longer one
+    #[arg(long, global = true, default_value_t = 0.5)]
+    title_similarity: f64,
+
+    #[arg(long, global = true)]
+    block_rules: Option<String>,
 }
 
 pub struct Commandline {
@@ -155,6 +402,15 @@ pub struct Commandline {
 }
 
 impl Commandline {
+    // a commandline built from the given arguments instead of the process ones
+    #[cfg(test)]
+    pub fn from_args(args: &[&str]) -> Self {
+        Self {
+            args: Args::parse_from(std::iter::once("pts").chain(args.iter().copied())),
+            content_ids_vec: CONTENT_IDS.iter().map(|&s| s.to_string()).collect(),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn copy(&self) -> Commandline {
         Self {
@@ -175,7 +431,7 @@ impl Commandline {
                     Ok(d) => Some(d),
                     Err(err) => {
                         println!("{:?}", err);
-                        println!("required format is dd.mm.yyyy");
+                        println!("{}", Msg::DayFormat);
                         None
                     }
                 }
@@ -258,9 +514,9 @@ impl Commandline {
     pub fn today(&self) -> Option<NaiveDate> {
         match &self.args.today {
             None => None,
-            Some(None) => Some(Utc::now().date_naive()),
+            Some(None) => Some(broadcast_day(Utc::now(), self.day_start(), self.utc())),
             Some(Some(s)) => {
-                let mut today = Utc::now().date_naive();
+                let mut today = broadcast_day(Utc::now(), self.day_start(), self.utc());
                 if s.contains("+") {
                     let mut count: u64 = 1;
                     if s != "+" {
@@ -297,10 +553,241 @@ impl Commandline {
         }
     }
 
+    // the broadcast day picked by --day or --today
+    pub fn selected_day(&self) -> Option<NaiveDate> {
+        self.day().or_else(|| self.today())
+    }
+
+    pub fn columns(&self) -> Option<Vec<String>> {
+        self.args
+            .columns
+            .as_ref()
+            .map(|s| s.split(',').map(|x| x.trim().to_string()).collect())
+    }
+
+    pub fn lang(&self) -> Lang {
+        match &self.args.lang {
+            None => Lang::detect(),
+            Some(s) => Lang::parse(s).unwrap_or(Lang::En),
+        }
+    }
+
+    pub fn plan(&self) -> Option<String> {
+        match &self.args.command {
+            Some(Command::Check { plan, .. }) => plan.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn plan_sheet(&self) -> Option<String> {
+        match &self.args.command {
+            Some(Command::Check { sheet, .. }) => sheet.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn fix_si(&self) -> Option<FixStrategy> {
+        match &self.args.command {
+            Some(Command::Fix {
+                target: FixTarget::Si { strategy, .. },
+            }) => Some(*strategy),
+            _ => None,
+        }
+    }
+
+    pub fn fix_output(&self) -> Option<String> {
+        match &self.args.command {
+            Some(Command::Fix {
+                target: FixTarget::Si { output, .. },
+            }) => output.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn fix_dry_run(&self) -> bool {
+        matches!(
+            &self.args.command,
+            Some(Command::Fix {
+                target: FixTarget::Si { dry_run: true, .. },
+            })
+        )
+    }
+
+    pub fn no_color(&self) -> bool {
+        self.args.no_color || self.args.ascii
+    }
+
+    pub fn ascii(&self) -> bool {
+        self.args.ascii
+    }
+
+    pub fn width(&self) -> Option<usize> {
+        self.args.width
+    }
+
+    pub fn input_encoding(&self) -> Option<&str> {
+        self.args.input_encoding.as_deref()
+    }
+
+    pub fn entries(&self) -> Option<&str> {
+        self.args.entries.as_deref()
+    }
+
+    pub fn logo_coverage(&self) -> bool {
+        self.args.logo_coverage
+    }
+
+    pub fn layout_conflicts(&self) -> bool {
+        self.args.layout_conflicts || self.args.layout_rules.is_some()
+    }
+
+    pub fn layout_rules(&self) -> Option<&str> {
+        self.args.layout_rules.as_deref()
+    }
+
+    pub fn compliance(&self) -> bool {
+        self.args.compliance || self.args.compliance_rules.is_some()
+    }
+
+    pub fn compliance_rules(&self) -> Option<&str> {
+        self.args.compliance_rules.as_deref()
+    }
+
+    pub fn advertising_time(&self) -> bool {
+        self.args.advertising_time
+    }
+
+    // seconds of advertising allowed per window
+    pub fn ad_limit(&self) -> i64 {
+        self.args.ad_limit
+    }
+
+    // window length in minutes
+    pub fn ad_window(&self) -> i64 {
+        self.args.ad_window.clamp(1, 24 * 60)
+    }
+
+    pub fn ad_content_ids(&self) -> Vec<String> {
+        self.args
+            .ad_content_ids
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+
+    pub fn interruptions(&self) -> bool {
+        self.args.interruptions
+    }
+
+    // minutes a programme has to run before it may be interrupted
+    pub fn min_segment(&self) -> i64 {
+        self.args.min_segment
+    }
+
+    // minutes from the start of one break to the start of the next
+    pub fn break_interval(&self) -> i64 {
+        self.args.break_interval
+    }
+
+    pub fn youth_protection(&self) -> bool {
+        self.args.youth_protection || self.args.youth_rules.is_some() || self.args.ratings.is_some()
+    }
+
+    pub fn youth_rules(&self) -> Option<&str> {
+        self.args.youth_rules.as_deref()
+    }
+
+    pub fn ratings(&self) -> Option<&str> {
+        self.args.ratings.as_deref()
+    }
+
+    pub fn identifiers(&self) -> bool {
+        self.args.identifiers || self.args.programid_pattern.is_some()
+    }
+
+    // '*' any characters, '?' one character, '#' a digit
+    pub fn programid_pattern(&self) -> Option<&str> {
+        self.args.programid_pattern.as_deref()
+    }
+
+    pub fn reruns(&self) -> bool {
+        self.args.reruns || self.args.rerun_rules.is_some()
+    }
+
+    pub fn rerun_rules(&self) -> Option<&str> {
+        self.args.rerun_rules.as_deref()
+    }
+
+    pub fn day_coverage(&self) -> bool {
+        self.args.day_coverage
+    }
+
+    // hour the broadcast day starts at
+    pub fn day_start(&self) -> u32 {
+        self.args.day_start.min(23)
+    }
+
+    pub fn sendepause(&self) -> bool {
+        self.args.sendepause || self.args.sendepause_rules.is_some()
+    }
+
+    pub fn sendepause_rules(&self) -> Option<&str> {
+        self.args.sendepause_rules.as_deref()
+    }
+
+    pub fn material_bounds(&self) -> bool {
+        self.args.material_bounds
+    }
+
+    pub fn material_status(&self) -> bool {
+        self.args.material_status
+    }
+
+    pub fn video_format(&self) -> bool {
+        self.args.video_format || self.args.format_rules.is_some()
+    }
+
+    pub fn format_rules(&self) -> Option<&str> {
+        self.args.format_rules.as_deref()
+    }
+
+    pub fn trailer_balls(&self) -> bool {
+        self.args.trailer_balls || self.args.ball_rules.is_some()
+    }
+
+    pub fn ball_rules(&self) -> Option<&str> {
+        self.args.ball_rules.as_deref()
+    }
+
+    pub fn epg_text(&self) -> bool {
+        self.args.epg_text || self.args.epg_rules.is_some()
+    }
+
+    pub fn epg_rules(&self) -> Option<&str> {
+        self.args.epg_rules.as_deref()
+    }
+
+    pub fn titles(&self) -> bool {
+        self.args.titles
+    }
+
+    pub fn title_similarity(&self) -> f64 {
+        self.args.title_similarity.clamp(0.0, 1.0)
+    }
+
+    pub fn block_rules(&self) -> Option<&str> {
+        self.args.block_rules.as_deref()
+    }
+
     pub fn display_sievents(&self) -> bool {
         self.args.display_sievents
     }
 
+    pub fn werbungen_text(&self) -> &str {
+        &self.args.werbungen
+    }
+
     pub fn werbungen(&self) -> Option<Vec<Vec<String>>> {
         let werbungen: Vec<Vec<String>> = self
             .args
@@ -380,6 +867,10 @@ impl Commandline {
         self.csv() != "YOU_PICK_A_CSV"
     }
 
+    pub fn valid_range_text(&self) -> &str {
+        &self.args.valid_range
+    }
+
     pub fn valid_range(&self) -> Option<Range> {
         let default_range: Range = match serde_json::from_value::<Range>(json!({
             "startTime": "2000-01-01T01:00:00.000Z",
@@ -391,6 +882,10 @@ impl Commandline {
             Ok(range) => range,
         };
         let range_str = self.args.valid_range.to_string();
+        if range_str.contains("..") {
+            return friendly_range(&range_str, self.utc())
+                .filter(|range| range.start_time < range.end_time);
+        }
         match serde_json::from_str::<Range>(&range_str) {
             Err(error) => {
                 if range_str == DEFAULT_VALID_RANGE {
@@ -432,14 +927,7 @@ impl Commandline {
                                 if self.debug() {
                                     println!("{:?}", json);
                                 }
-                                if json.start_time < json.end_time {
-                                    Some(json)
-                                } else {
-                                    if self.debug() {
-                                        println!("Invalid range: {:?}", json);
-                                    }
-                                    None
-                                }
+                                (json.start_time < json.end_time).then_some(json)
                             }
                         }
                     } else {
@@ -449,16 +937,7 @@ impl Commandline {
                     None
                 }
             }
-            Ok(range) => {
-                if range.start_time < range.end_time {
-                    Some(range)
-                } else {
-                    if self.debug() {
-                        println!("Invalid range: {:?}", range);
-                    }
-                    None
-                }
-            }
+            Ok(range) => (range.start_time < range.end_time).then_some(range),
         }
     }
 
@@ -525,6 +1004,24 @@ impl Commandline {
     pub fn no_option(&self) -> bool {
         !(self.look_for_illegalevents()
             || self.all()
+            || self.plan().is_some()
+            || self.fix_si().is_some()
+            || self.logo_coverage()
+            || self.layout_conflicts()
+            || self.compliance()
+            || self.advertising_time()
+            || self.interruptions()
+            || self.youth_protection()
+            || self.identifiers()
+            || self.reruns()
+            || self.day_coverage()
+            || self.sendepause()
+            || self.material_bounds()
+            || self.material_status()
+            || self.video_format()
+            || self.trailer_balls()
+            || self.epg_text()
+            || self.titles()
             || self.write_csv()
             || self.write_xlsx()
             || self.ps_event()
@@ -550,3 +1047,110 @@ impl Commandline {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lang_rejects_unknown_languages() {
+        assert!(Args::try_parse_from(["pts", "--lang", "de_DE"]).is_ok());
+        assert!(Args::try_parse_from(["pts", "--lang", "xx"]).is_err());
+        assert_eq!(Commandline::from_args(&["--lang", "en"]).lang(), Lang::En);
+    }
+
+    fn time(text: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    fn range(text: &str, utc: bool) -> Option<(String, String)> {
+        let format = |time: DateTime<Utc>| time.format("%Y-%m-%dT%H:%M").to_string();
+        friendly_range(text, utc).map(|range| (format(range.start_time), format(range.end_time)))
+    }
+
+    fn pair(start: &str, end: &str) -> Option<(String, String)> {
+        Some((start.to_string(), end.to_string()))
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(parse_offset("+6h"), Some(Duration::hours(6)));
+        assert_eq!(parse_offset(" +90m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_offset("+1d"), Some(Duration::days(1)));
+        assert_eq!(parse_offset("6h"), None);
+        assert_eq!(parse_offset("+6x"), None);
+        assert_eq!(parse_offset("+"), None);
+    }
+
+    #[test]
+    fn points() {
+        let today = Utc::now().date_naive();
+        assert_eq!(
+            parse_point("2024-05-01T20:00", true),
+            Some(time("2024-05-01T20:00"))
+        );
+        assert_eq!(
+            parse_point("01.05.2024 20:00:30", true),
+            Some(time("2024-05-01T20:00") + Duration::seconds(30))
+        );
+        assert_eq!(
+            parse_point("2024-05-01", true),
+            Some(time("2024-05-01T00:00"))
+        );
+        assert_eq!(
+            parse_point("today 18:00", true),
+            Some(today.and_hms_opt(18, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_point("Morgen", true),
+            today.succ_opt().map(|d| d.and_time(NaiveTime::MIN))
+        );
+        assert_eq!(parse_point("32.05.2024 20:00", true), None);
+        assert_eq!(parse_point("today 25:00", true), None);
+    }
+
+    #[test]
+    fn friendly_ranges() {
+        let start = Utc::now().date_naive().and_hms_opt(18, 0, 0).unwrap();
+        let format = |time: NaiveDateTime| time.format("%Y-%m-%dT%H:%M").to_string();
+        let end = format(start + Duration::hours(6));
+        assert_eq!(range("today 18:00..+6h", true), pair(&format(start), &end));
+        assert_eq!(
+            range("2024-05-01T20:00..2024-05-01T23:00", true),
+            pair("2024-05-01T20:00", "2024-05-01T23:00")
+        );
+        assert_eq!(
+            range("01.05.2024 20:00..+90m", true),
+            pair("2024-05-01T20:00", "2024-05-01T21:30")
+        );
+        crate::pts_loader::fixtures::berlin();
+        assert_eq!(
+            range("01.05.2024 20:00..+90m", false),
+            pair("2024-05-01T18:00", "2024-05-01T19:30")
+        );
+        assert_eq!(range("01.05.2024 20:00", true), None);
+        assert_eq!(range("01.05.2024 20:00..+90", true), None);
+    }
+
+    #[test]
+    fn invalid_and_reversed_ranges() {
+        let valid_range =
+            |text: &str| Commandline::from_args(&["--utc", "--valid-range", text]).valid_range();
+        assert!(valid_range("2024-05-01T20:00..2024-05-01T23:00").is_some());
+        assert!(valid_range("2024-05-01T23:00..2024-05-01T20:00").is_none());
+        assert!(
+            valid_range("startTime=2024-05-01T20:00:00.000Z;endTime=2024-05-01T23:00:00.000Z")
+                .is_some()
+        );
+        assert!(
+            valid_range("startTime=2024-05-01T23:00:00.000Z;endTime=2024-05-01T20:00:00.000Z")
+                .is_none()
+        );
+        assert!(valid_range(
+            r#"{"startTime": "2024-05-01T23:00:00.000Z", "endTime": "2024-05-01T20:00:00.000Z"}"#
+        )
+        .is_none());
+        assert!(valid_range("yesterday").is_none());
+        assert!(Commandline::from_args(&[]).valid_range().is_some());
+    }
+}
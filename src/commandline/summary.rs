@@ -1,6 +1,7 @@
 use colored::Colorize;
 
 use crate::commandline::commandline::Commandline;
+use crate::utils::messages::Msg;
 pub struct Summary {
     pub time_errors: i64,
     pub id_errors: i64,
@@ -17,6 +18,24 @@ pub struct Summary {
     pub content_to_long_error: usize,
     pub puffer_schleife_error: i64,
     pub alte_tafel_error: i64,
+    pub plan_missing_error: i64,
+    pub plan_extra_error: i64,
+    pub plan_order_error: i64,
+    pub plan_length_error: i64,
+    pub logo_coverage_error: i64,
+    pub layout_conflict_error: i64,
+    pub compliance_error: i64,
+    pub advertising_error: i64,
+    pub interruption_error: i64,
+    pub youth_protection_error: i64,
+    pub identifier_error: i64,
+    pub day_coverage_error: i64,
+    pub sendepause_error: i64,
+    pub offset_error: i64,
+    pub material_error: i64,
+    pub format_error: i64,
+    pub epg_text_error: i64,
+    pub title_error: i64,
 }
 
 impl Summary {
@@ -37,120 +56,96 @@ impl Summary {
             content_to_long_error: 0,
             puffer_schleife_error: 0,
             alte_tafel_error: 0,
+            plan_missing_error: 0,
+            plan_extra_error: 0,
+            plan_order_error: 0,
+            plan_length_error: 0,
+            logo_coverage_error: 0,
+            layout_conflict_error: 0,
+            compliance_error: 0,
+            advertising_error: 0,
+            interruption_error: 0,
+            youth_protection_error: 0,
+            identifier_error: 0,
+            day_coverage_error: 0,
+            sendepause_error: 0,
+            offset_error: 0,
+            material_error: 0,
+            format_error: 0,
+            epg_text_error: 0,
+            title_error: 0,
         }
     }
 
+    pub fn print_count(count: i64, msg: Msg) {
+        println!(
+            "{:3} {}",
+            if count == 0 {
+                format!("{}", count).green()
+            } else {
+                format!("{}", count).red()
+            },
+            msg
+        );
+    }
+
     pub fn print(&self, cmd: &Commandline) {
-        if cmd.all() || cmd.ps_event() || cmd.vaerrors() || cmd.sierrors() {
-            println!("{}", "Error Summary:");
+        if cmd.all()
+            || cmd.ps_event()
+            || cmd.vaerrors()
+            || cmd.sierrors()
+            || cmd.plan().is_some()
+            || cmd.logo_coverage()
+            || cmd.layout_conflicts()
+            || cmd.compliance()
+            || cmd.advertising_time()
+            || cmd.interruptions()
+            || cmd.youth_protection()
+            || cmd.identifiers()
+            || cmd.day_coverage()
+            || cmd.sendepause()
+            || cmd.material_bounds()
+            || cmd.material_status()
+            || cmd.video_format()
+            || cmd.trailer_balls()
+            || cmd.epg_text()
+            || cmd.titles()
+        {
+            println!("{}", Msg::ErrorSummary);
         }
 
         if cmd.all() || cmd.ps_event() {
-            println!(
-                "{:3} time errors",
-                if self.time_errors > 0 {
-                    format!("{}", self.time_errors).red()
-                } else {
-                    format!("{}", self.time_errors).green()
-                }
-            );
-
-            println!(
-                "{:3} id errors",
-                if self.id_errors > 0 {
-                    format!("{}", self.id_errors).red()
-                } else {
-                    format!("{}", self.id_errors).green()
-                }
-            );
-
-            println!(
-                "{:3} logo errors",
-                if self.logo_errors > 0 {
-                    format!("{}", self.logo_errors).red()
-                } else {
-                    format!("{}", self.logo_errors).green()
-                }
-            );
-            println!(
-                "{:3} special event errors",
-                if self.special_event_errors == 0 {
-                    format!("{}", 0).green()
-                } else {
-                    format!("{}", self.special_event_errors).red()
-                }
-            );
-
-            println!(
-                "{:3} length errors",
-                if self.length_error == 0 {
-                    format!("{}", 0).green()
-                } else {
-                    format!("{}", self.length_error).red()
-                }
-            );
-
-            println!(
-                "{:3} si length errors",
-                if self.si_length_error == 0 {
-                    format!("{}", 0).green()
-                } else {
-                    format!("{}", self.si_length_error).red()
-                }
-            );
-
-            println!(
-                "{:3} commercial length errors",
-                if self.commercial_error == 0 {
-                    format!("{}", 0).green()
-                } else {
-                    format!("{}", self.commercial_error).red()
-                }
-            );
+            Summary::print_count(self.time_errors, Msg::TimeErrors);
+            Summary::print_count(self.id_errors, Msg::IdErrors);
+            Summary::print_count(self.logo_errors, Msg::LogoErrors);
+            Summary::print_count(self.special_event_errors, Msg::SpecialEventErrors);
+            Summary::print_count(self.length_error, Msg::LengthErrors);
+            Summary::print_count(self.si_length_error, Msg::SiLengthErrors);
+            Summary::print_count(self.commercial_error, Msg::CommercialLengthErrors);
         }
 
         if cmd.all() || cmd.vaerrors() {
-            println!(
-                "{:3} vaerrors",
-                if self.va_errors == 0 {
-                    format!("{}", 0).green()
-                } else {
-                    format!("{}", self.va_errors).red()
-                }
-            );
+            Summary::print_count(self.va_errors, Msg::VaErrors);
         }
 
         if cmd.all() || cmd.sierrors() {
-            println!(
-                "{:3} sierrors",
-                if self.si_errors == 0 {
-                    format!("{}", self.si_errors).green()
-                } else {
-                    format!("{}", self.si_errors).red()
-                }
-            );
+            Summary::print_count(self.si_errors, Msg::SiErrors);
         }
 
         if cmd.all() || cmd.missing_texts() {
-            println!(
-                "{:3} missing_texts",
-                if self.text_error == 0 {
-                    format!("{}", self.text_error).green()
-                } else {
-                    format!("{}", self.text_error).red()
-                }
-            );
+            Summary::print_count(self.text_error, Msg::MissingTexts);
         }
 
-        if cmd.all() {
-            println!(
-                "{:3} trailer balls mixup",
-                if self.trailer_balls_error == 0 {
-                    format!("{}", self.trailer_balls_error).green()
-                } else {
-                    format!("{}", self.trailer_balls_error).red()
-                }
-            );
+        if cmd.all() || cmd.epg_text() {
+            Summary::print_count(self.epg_text_error, Msg::EpgTextErrors);
+        }
+
+        if cmd.all() || cmd.titles() {
+            Summary::print_count(self.title_error, Msg::TitleErrors);
+        }
+
+        if cmd.all() || cmd.trailer_balls() {
+            Summary::print_count(self.trailer_balls_error, Msg::TrailerBallsMixup);
         }
 
         /*
@@ -166,41 +161,75 @@ impl Summary {
         }
         */
 
-        if cmd.all() && cmd.fluid_csv().is_some() {
-            println!(
-                "{:3} content which is to long",
-                if self.content_to_long_error == 0 {
-                    format!("{}", self.content_to_long_error).green()
-                } else {
-                    format!("{}", self.content_to_long_error).red()
-                }
-            );
+        if (cmd.all() || cmd.material_bounds()) && cmd.fluid_csv().is_some() {
+            Summary::print_count(self.content_to_long_error as i64, Msg::ContentTooLong);
+        }
+
+        if cmd.all() || cmd.material_bounds() {
+            Summary::print_count(self.offset_error, Msg::OffsetErrors);
+        }
+
+        if (cmd.all() || cmd.material_status()) && cmd.fluid_csv().is_some() {
+            Summary::print_count(self.material_error, Msg::MaterialErrors);
+        }
+
+        if (cmd.all() || cmd.video_format()) && cmd.fluid_csv().is_some() {
+            Summary::print_count(self.format_error, Msg::FormatErrors);
         }
 
         if cmd.all() {
-            println!(
-                "{:3} pufferschleifen errors",
-                if self.puffer_schleife_error == 0 {
-                    format!("{}", self.puffer_schleife_error).green()
-                } else {
-                    format!("{}", self.puffer_schleife_error).red()
-                }
-            );
+            Summary::print_count(self.puffer_schleife_error, Msg::PufferschleifeErrors);
         }
 
         if cmd.all() {
-            println!(
-                "{:3} Alte Tafel errors",
-                if self.alte_tafel_error == 0 {
-                    format!("{}", self.alte_tafel_error).green()
-                } else {
-                    format!("{}", self.alte_tafel_error).red()
-                }
-            );
+            Summary::print_count(self.alte_tafel_error, Msg::AlteTafelErrors);
             if self.alte_tafel_error > 0 {
-                println!("please use 'Dranbleiben - gleich geht's lo' 3ecea6b8084f35c2634d");
-                println!("please use 'Bald geht's hier weiter mit de' 02e9d82071c7735de5cd");
+                println!("{}", Msg::UseDranbleiben);
+                println!("{}", Msg::UseBaldGehtsWeiter);
             }
         }
+
+        if cmd.all() || cmd.logo_coverage() {
+            Summary::print_count(self.logo_coverage_error, Msg::LogoCoverageErrors);
+        }
+
+        if cmd.all() || cmd.layout_conflicts() {
+            Summary::print_count(self.layout_conflict_error, Msg::LayoutConflictErrors);
+        }
+
+        if cmd.all() || cmd.compliance() {
+            Summary::print_count(self.compliance_error, Msg::ComplianceErrors);
+        }
+
+        if cmd.all() || cmd.advertising_time() {
+            Summary::print_count(self.advertising_error, Msg::AdvertisingErrors);
+        }
+
+        if cmd.all() || cmd.interruptions() {
+            Summary::print_count(self.interruption_error, Msg::InterruptionErrors);
+        }
+
+        if cmd.all() || cmd.youth_protection() {
+            Summary::print_count(self.youth_protection_error, Msg::YouthErrors);
+        }
+
+        if cmd.all() || cmd.identifiers() {
+            Summary::print_count(self.identifier_error, Msg::IdentifierErrors);
+        }
+
+        if cmd.all() || cmd.day_coverage() {
+            Summary::print_count(self.day_coverage_error, Msg::DayCoverageErrors);
+        }
+
+        if cmd.all() || cmd.sendepause() {
+            Summary::print_count(self.sendepause_error, Msg::PauseErrors);
+        }
+
+        if cmd.plan().is_some() {
+            Summary::print_count(self.plan_missing_error, Msg::PlanMissingErrors);
+            Summary::print_count(self.plan_extra_error, Msg::PlanExtraErrors);
+            Summary::print_count(self.plan_order_error, Msg::PlanOrderErrors);
+            Summary::print_count(self.plan_length_error, Msg::PlanLengthErrors);
+        }
     }
 }
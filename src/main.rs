@@ -4,120 +4,351 @@ mod utils;
 
 use crate::utils::fluid::Fluid;
 use colored::Colorize;
-use commandline::commandline::Commandline;
+use commandline::commandline::{Commandline, FixStrategy};
 use commandline::summary::Summary;
 use pts_loader::dataset::DataSet;
+use pts_loader::reruns;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use utils::ball_rules::{self, BallRules};
+use utils::block_rules::{self, BlockRules};
+use utils::compliance_rules::{self, ComplianceRules};
+use utils::encoding;
+use utils::epg_rules;
 use utils::fluid;
+use utils::format_rules;
+use utils::layout_rules;
+use utils::messages::{self, Msg};
+use utils::plan::Plan;
+use utils::rerun_rules::{self, RerunRules};
+use utils::rules::load_rules;
+use utils::sendepause_rules::{self, SendepauseRules};
+use utils::youth_rules::{self, YouthRules};
 
-fn main() -> std::io::Result<()> {
-    let cmd = Commandline::parse();
-    if cmd.update_fluid_data_base() {
-        match fluid::download_fluid_data_base("test") {
-            Ok(file_path) => println!("success: {}", file_path),
-            Err(err) => eprintln!("{}", err),
+// the rules from `path` or the built-in ones, the error names the file
+fn read_rules<T: DeserializeOwned>(
+    path: Option<&str>,
+    default: &str,
+    error: Msg,
+) -> Result<T, String> {
+    load_rules(path, default).map_err(|e| error.fill(&[&path.unwrap_or_default(), &e]))
+}
+
+fn youth_rules(cmd: &Commandline) -> Result<YouthRules, Box<dyn Error>> {
+    let path = cmd.youth_rules();
+    let mut rules: YouthRules = load_rules(path, youth_rules::DEFAULT_RULES)
+        .map_err(|e| format!("{}: {}", path.unwrap_or_default(), e))?;
+    if let Some(path) = cmd.ratings() {
+        rules
+            .load_ratings(path)
+            .map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(rules)
+}
+
+fn sendepause_rules(cmd: &Commandline) -> Result<SendepauseRules, String> {
+    let default = sendepause_rules::DEFAULT_RULES;
+    read_rules(cmd.sendepause_rules(), default, Msg::PauseRulesError)
+}
+
+fn ball_rules(cmd: &Commandline) -> Result<BallRules, String> {
+    read_rules(cmd.ball_rules(), ball_rules::DEFAULT_RULES, Msg::BallRulesError)
+}
+
+fn check(mut dataset: DataSet, cmd: &Commandline, fluid: &Fluid) {
+    let mut summary = Summary::new();
+
+    if cmd.all() || cmd.ps_event() {
+        dataset.print_special_events(&mut summary, cmd, fluid);
+    }
+
+    if cmd.all() || cmd.vaerrors() {
+        dataset.print_va_errors(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.sierrors() {
+        dataset.print_si_errors(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.missing_texts() {
+        dataset.print_missing_text_errors(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.epg_text() {
+        match read_rules(cmd.epg_rules(), epg_rules::DEFAULT_RULES, Msg::EpgRulesError) {
+            Ok(rules) => dataset.check_epg_text(&mut summary, cmd, &rules),
+            Err(e) => println!("{}", e),
         }
     }
 
-    if cmd.filename() == "YOU_PICK_A_FILE" {
-        println!("{:?}", "file not found");
-        Commandline::print_help();
+    if cmd.display_sievents() {
+        println!("\n{}", Msg::SiEvents);
+        dataset.display_sievents(cmd);
+    }
+
+    if cmd.display_trailers() {
+        println!("\n{}", Msg::Trailer);
+        dataset.display_trailers(cmd);
+    }
+
+    if cmd.all() || cmd.check_all_contentids() {
+        println!("\n{}", Msg::AllContentIds);
+        dataset.display_all_content_id_errors(&mut summary, cmd);
+    }
+
+    if (cmd.all() || cmd.material_bounds()) && cmd.fluid_csv().is_some() {
+        dataset.list_vaevents_with_length_errors(&mut summary, cmd, fluid);
+    }
+
+    if cmd.all() || cmd.material_bounds() {
+        dataset.list_segment_offset_errors(&mut summary, cmd);
+    }
+
+    if (cmd.all() || cmd.material_status()) && cmd.fluid_csv().is_some() {
+        dataset.check_material_status(&mut summary, cmd, fluid);
+    }
+
+    if (cmd.all() || cmd.video_format()) && cmd.fluid_csv().is_some() {
+        match read_rules(cmd.format_rules(), format_rules::DEFAULT_RULES, Msg::FormatRulesError) {
+            Ok(rules) => dataset.check_video_format(&mut summary, cmd, &rules, fluid),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    if cmd.all() || cmd.day_coverage() {
+        dataset.check_day_coverage(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.identifiers() {
+        dataset.check_identifiers(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.logo_coverage() {
+        dataset.check_logo_coverage(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.layout_conflicts() {
+        match read_rules(cmd.layout_rules(), layout_rules::DEFAULT_RULES, Msg::LayoutRulesError) {
+            Ok(rules) => dataset.check_layout_conflicts(&mut summary, cmd, &rules),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    if cmd.all() || cmd.sendepause() {
+        match sendepause_rules(cmd) {
+            Ok(rules) => dataset.check_sendepause(&mut summary, cmd, &rules),
+            Err(e) => println!("{}", e),
+        }
+    }
+
+    if cmd.all() || cmd.titles() {
+        match (sendepause_rules(cmd), ball_rules(cmd)) {
+            (Ok(pauses), Ok(balls)) => dataset.check_titles(&mut summary, cmd, &pauses, &balls),
+            (Err(e), _) | (_, Err(e)) => println!("{}", e),
+        }
+    }
+
+    if cmd.all() || cmd.interruptions() {
+        dataset.check_interruptions(&mut summary, cmd);
+    }
+
+    if cmd.all() || cmd.advertising_time() {
+        dataset.check_advertising_time(&mut summary, cmd);
+    }
+
+    let compliance: Option<ComplianceRules> = if cmd.all() || cmd.compliance() {
+        let default = compliance_rules::DEFAULT_RULES;
+        read_rules(cmd.compliance_rules(), default, Msg::ComplianceRulesError)
+            .inspect_err(|e| println!("{}", e))
+            .ok()
     } else {
-        match DataSet::init(cmd.filename()) {
-            Ok(mut dataset) => {
-                let mut summary = Summary::new();
+        None
+    };
+    if let Some(rules) = &compliance {
+        dataset.check_compliance(&mut summary, cmd, rules, fluid);
+    }
 
-                let mut fluid_data_base = Fluid::init();
+    let balls = if cmd.all() || cmd.trailer_balls() {
+        ball_rules(cmd).inspect_err(|e| println!("{}", e)).ok()
+    } else {
+        None
+    };
+    if let Some(rules) = &balls {
+        dataset.trailers_and_balls_mixup(&mut summary, cmd, rules);
+    }
 
-                if cmd.fluid_csv().is_some() {
-                    fluid_data_base.load(cmd.fluid_csv().unwrap());
-                }
+    if cmd.all() || cmd.youth_protection() {
+        match youth_rules(cmd) {
+            Ok(rules) => dataset.check_youth_protection(&mut summary, cmd, &rules, fluid),
+            Err(e) => println!("{}", Msg::YouthRulesError.fill(&[&e])),
+        }
+    }
 
-                if cmd.all() || cmd.ps_event() {
-                    dataset.print_special_events(&mut summary, &cmd, &fluid_data_base);
-                }
+    if let Some(path) = cmd.plan() {
+        match Plan::load(&path, cmd.plan_sheet().as_deref(), cmd.fps().unwrap_or(25)) {
+            Ok(plan) => dataset.check_plan(&mut summary, cmd, &plan),
+            Err(e) => println!("{}", Msg::PlanLoadError.fill(&[&path, &e])),
+        }
+    }
 
-                if cmd.all() || cmd.vaerrors() {
-                    dataset.print_va_errors(&mut summary, &cmd);
-                }
+    summary.print(cmd);
 
-                if cmd.all() || cmd.sierrors() {
-                    dataset.print_si_errors(&mut summary, &cmd);
+    if cmd.write_csv() {
+        match dataset.write_special_events_csv(cmd, fluid, compliance.as_ref(), balls.as_ref()) {
+            Err(e) => {
+                if cmd.debug() {
+                    println!("{}", e);
                 }
+            }
+            Ok(..) => {
+                let path = dataset.export_path(&cmd.csv());
+                println!("{}", Msg::WroteCsv.fill(&[&path.cyan()]))
+            }
+        }
+    }
 
-                if cmd.all() || cmd.missing_texts() {
-                    dataset.print_missing_text_errors(&mut summary, &cmd);
+    if cmd.write_xlsx() {
+        match dataset.write_special_events_xlsx(cmd, fluid, compliance.as_ref(), balls.as_ref()) {
+            Err(e) => {
+                if cmd.debug() {
+                    println!("{}", e);
                 }
+            }
+            Ok(..) => {
+                let path = dataset.export_path(&cmd.xlsx());
+                println!("{}", Msg::WroteXlsx.fill(&[&path.cyan()]))
+            }
+        }
+    }
 
-                if cmd.display_sievents() {
-                    println!("\nSiEvents");
-                    dataset.display_sievents(&cmd);
-                }
+    if cmd.update_werbungen() {
+        match dataset.update_commercials(cmd) {
+            Err(e) => println!("{}", e),
+            Ok(file) => println!("{}", Msg::UpdatedPts.fill(&[&file])),
+        }
+    }
 
-                if cmd.display_trailers() {
-                    println!("\nTrailer");
-                    dataset.display_trailers(&cmd);
-                }
+    match cmd.illegalevents() {
+        None => (),
+        Some(illegals) => dataset.look_for_illegals(&illegals, cmd),
+    }
+}
 
-                if cmd.all() || cmd.check_all_contentids() {
-                    println!("{}", "\nAll content ids");
-                    dataset.display_all_content_id_errors(&mut summary, &cmd);
-                }
+fn fix(dataset: DataSet, cmd: &Commandline, strategy: FixStrategy) {
+    match dataset.fix_si_events(cmd, strategy) {
+        Err(e) => println!("{}", e),
+        Ok(Some(path)) => println!("{}", Msg::WroteFixedPts.fill(&[&path.cyan()])),
+        Ok(None) => (),
+    }
+}
 
-                if cmd.all() && cmd.fluid_csv().is_some() {
-                    dataset.list_vaevents_with_length_errors(&mut summary, &cmd, &fluid_data_base);
-                }
+fn report_error(e: &dyn std::fmt::Display, cmd: &Commandline) {
+    if format!("{}", e).contains("os error 2") {
+        println!(
+            "{}",
+            Msg::FileNotFound.fill(&[&format!("{:?}", cmd.filename())])
+        );
+    } else if let Some(label) = cmd
+        .input_encoding()
+        .filter(|l| encoding::lookup(l).is_none())
+    {
+        println!("{}", Msg::UnknownEncoding.fill(&[&format!("{:?}", label)]));
+    } else if cmd.debug() {
+        println!("{}", e);
+    }
+    Commandline::print_help()
+}
 
-                summary.print(&cmd);
+fn main() -> std::io::Result<()> {
+    let cmd = Commandline::parse();
+    messages::set_lang(cmd.lang());
+    if cmd.no_color() {
+        colored::control::set_override(false);
+    }
+    if !cmd.werbungen_text().is_empty() {
+        println!("{}", Msg::WerbungenDeprecated);
+    }
+    if cmd.update_fluid_data_base() {
+        match fluid::download_fluid_data_base("test") {
+            Ok(file_path) => println!("{}", Msg::DownloadSuccess.fill(&[&file_path])),
+            Err(err) => eprintln!("{}", err),
+        }
+    }
 
-                if cmd.write_csv() {
-                    match dataset.write_special_events_csv(&cmd, &fluid_data_base) {
-                        Err(e) => {
-                            if cmd.debug() {
-                                println!("{}", e);
-                            }
-                        }
-                        Ok(..) => println!("Wrote csv to '{}'", cmd.csv().to_string().cyan()),
-                    }
+    if cmd.filename() == "YOU_PICK_A_FILE" {
+        println!("{}", Msg::NoFileGiven);
+        Commandline::print_help();
+    } else {
+        // checking everything instead of the range asked for would be wrong
+        let Some(range) = cmd.valid_range() else {
+            eprintln!("{}", Msg::InvalidRange.fill(&[&cmd.valid_range_text()]));
+            std::process::exit(2);
+        };
+        match DataSet::load(cmd.filename(), cmd.entries(), cmd.input_encoding()) {
+            Ok(datasets) => {
+                let mut fluid_data_base = Fluid::init();
+
+                if cmd.fluid_csv().is_some() {
+                    fluid_data_base.load(cmd.fluid_csv().unwrap());
                 }
 
-                if cmd.write_xlsx() {
-                    match dataset.write_special_events_xlsx(&cmd, &fluid_data_base) {
-                        Err(e) => {
-                            if cmd.debug() {
-                                println!("{}", e);
-                            }
-                        }
-                        Ok(..) => println!("Wrote xlsx to '{}'", cmd.xlsx().to_string().cyan()),
-                    }
+                if datasets.is_empty() {
+                    println!("{}", Msg::NoEntries.fill(&[cmd.filename()]));
                 }
+                // reruns are counted across all files of a batch
+                let rerun_rules: Option<RerunRules> = if cmd.all() || cmd.reruns() {
+                    let default = rerun_rules::DEFAULT_RULES;
+                    read_rules(cmd.rerun_rules(), default, Msg::RerunRulesError)
+                        .inspect_err(|e| println!("{}", e))
+                        .ok()
+                } else {
+                    None
+                };
+                let block_rules: Option<BlockRules> = cmd.block_rules().and_then(|path| {
+                    read_rules(Some(path), block_rules::DEFAULT_RULES, Msg::BlockRulesError)
+                        .inspect_err(|e| println!("{}", e))
+                        .ok()
+                });
+                let mut airings = Vec::new();
 
-                if cmd.update_werbungen() {
-                    match dataset.update_commercials(&cmd) {
-                        Err(e) => println!("{}", e),
-                        Ok(file) => println!("updated pts file: {}", file),
+                let batch = datasets.len() > 1;
+                for (name, dataset) in datasets {
+                    if batch {
+                        println!("\n{}", name.bold());
+                    }
+                    match dataset {
+                        Ok(mut dataset) => {
+                            if let Some(rules) = &block_rules {
+                                dataset.set_block_rules(rules.clone());
+                            }
+                            if let Some(day) = cmd.selected_day() {
+                                dataset.select_day(day, cmd.day_start(), cmd.utc());
+                            }
+                            dataset.select_range(&range);
+                            if let Some(rules) = &rerun_rules {
+                                airings.extend(dataset.airings(rules, &fluid_data_base));
+                            }
+                            match cmd.fix_si() {
+                                Some(strategy) => fix(dataset, &cmd, strategy),
+                                None => check(dataset, &cmd, &fluid_data_base),
+                            }
+                        }
+                        Err(e) if batch => println!("{}", Msg::EntryError.fill(&[&name, &e])),
+                        Err(e) => report_error(&e, &cmd),
                     }
                 }
 
-                match cmd.illegalevents() {
-                    None => (),
-                    Some(illegals) => dataset.look_for_illegals(&illegals, &cmd),
+                if let Some(rules) = &rerun_rules {
+                    println!();
+                    let errors = reruns::print(&airings, rules, &cmd);
+                    Summary::print_count(errors, Msg::RerunErrors);
                 }
 
                 if cmd.no_option() {
                     Commandline::print_help();
                 }
             }
-            Err(e) => {
-                if format!("{}", e).contains("os error 2") {
-                    println!(
-                        "Das System kann die angegebene Datei {:?} nicht finden.",
-                        cmd.filename()
-                    );
-                } else if cmd.debug() {
-                    println!("{}", e);
-                }
-                Commandline::print_help()
-            }
+            Err(e) => report_error(&e, &cmd),
         }
     }
 
@@ -0,0 +1,137 @@
+use crate::pts_loader::dataset::{local_time, utc_time};
+use chrono::{DateTime, Duration, NaiveTime, Timelike, Utc};
+
+// start and end in utc of the clock window `time` lies in, windows are aligned
+// to midnight and end there. A window can be shorter or longer on the days the
+// clocks change, the times of the hour that happens twice count to the last
+// window before 03:00.
+fn window_bounds(time: DateTime<Utc>, window: i64, utc: bool) -> (DateTime<Utc>, DateTime<Utc>) {
+    let local = local_time(time, utc);
+    let midnight = local.date().and_time(NaiveTime::MIN);
+    let bound = |minutes: i64| utc_time(midnight + Duration::minutes(minutes.min(24 * 60)), utc);
+    let minutes = (local.hour() * 60 + local.minute()) as i64;
+    let mut start = minutes - minutes % window;
+    while bound(start + window) <= time {
+        start += window;
+    }
+    (bound(start), bound(start + window))
+}
+
+// advertising milliseconds per window of local time (utc with `utc`), spots
+// crossing a window border are split between both windows
+pub fn per_window(
+    spots: &[(DateTime<Utc>, DateTime<Utc>)],
+    window: i64,
+    utc: bool,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>, i64)> {
+    let window = window.clamp(1, 24 * 60);
+    let mut totals: Vec<(DateTime<Utc>, DateTime<Utc>, i64)> = Vec::new();
+    for &(start, end) in spots {
+        let mut position = start;
+        while position < end {
+            let (from, to) = window_bounds(position, window, utc);
+            let until = to.min(end);
+            let ms = (until - position).num_milliseconds();
+            match totals.iter_mut().find(|(w, _, _)| *w == from) {
+                Some((_, _, total)) => *total += ms,
+                None => totals.push((from, to, ms)),
+            }
+            position = until;
+        }
+    }
+    totals.sort();
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{at, berlin};
+
+    fn spot(start: &str, end: &str) -> (DateTime<Utc>, DateTime<Utc>) {
+        (at(start), at(end))
+    }
+
+    #[test]
+    fn spots_are_summed_per_clock_hour() {
+        let spots = [
+            spot("18:10:00", "18:13:00"),
+            spot("18:40:00", "18:42:30"),
+            spot("20:00:00", "20:00:30"),
+        ];
+        assert_eq!(
+            per_window(&spots, 60, true),
+            [
+                (at("18:00:00"), at("19:00:00"), 330_000),
+                (at("20:00:00"), at("21:00:00"), 30_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn spots_crossing_a_border_are_split() {
+        let spots = [spot("18:58:00", "19:03:00")];
+        assert_eq!(
+            per_window(&spots, 60, true),
+            [
+                (at("18:00:00"), at("19:00:00"), 120_000),
+                (at("19:00:00"), at("20:00:00"), 180_000),
+            ]
+        );
+        assert_eq!(
+            per_window(&spots, 20, true),
+            [
+                (at("18:40:00"), at("19:00:00"), 120_000),
+                (at("19:00:00"), at("19:20:00"), 180_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn windows_end_at_midnight() {
+        // 7 hour windows start at 00:00, 07:00, 14:00 and 21:00
+        let spots = [spot("2024-05-01T23:59:00", "2024-05-02T00:01:00")];
+        assert_eq!(
+            per_window(&spots, 7 * 60, true),
+            [
+                (at("21:00:00"), at("2024-05-02T00:00:00"), 60_000),
+                (at("2024-05-02T00:00:00"), at("2024-05-02T07:00:00"), 60_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn windows_across_dst() {
+        berlin();
+        // 01:50 to 03:10 local, the clocks go forward at 02:00
+        let spots = [spot("2024-03-31T00:50:00", "2024-03-31T01:10:00")];
+        assert_eq!(
+            per_window(&spots, 60, false),
+            [
+                (at("2024-03-31T00:00:00"), at("2024-03-31T01:00:00"), 600_000),
+                (at("2024-03-31T01:00:00"), at("2024-03-31T02:00:00"), 600_000),
+            ]
+        );
+        // both 02:30 local fall into the two hours from 02:00 to 03:00
+        let spots = [
+            spot("2024-10-27T00:30:00", "2024-10-27T00:31:00"),
+            spot("2024-10-27T01:30:00", "2024-10-27T01:31:00"),
+            spot("2024-10-27T02:10:00", "2024-10-27T02:11:00"),
+        ];
+        assert_eq!(
+            per_window(&spots, 60, false),
+            [
+                (at("2024-10-27T00:00:00"), at("2024-10-27T02:00:00"), 120_000),
+                (at("2024-10-27T02:00:00"), at("2024-10-27T03:00:00"), 60_000),
+            ]
+        );
+        assert_eq!(
+            per_window(&spots, 20, false),
+            [
+                (at("2024-10-27T00:20:00"), at("2024-10-27T00:40:00"), 60_000),
+                (at("2024-10-27T00:40:00"), at("2024-10-27T02:00:00"), 60_000),
+                (at("2024-10-27T02:00:00"), at("2024-10-27T02:20:00"), 60_000),
+            ]
+        );
+    }
+}
@@ -1,4 +1,8 @@
 use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::programmes;
+use crate::pts_loader::special_event::SpecialEvent;
+use crate::utils::block_rules::BlockRules;
 
 #[derive(Clone)]
 pub enum Block<'a> {
@@ -19,18 +23,221 @@ impl<'a> Block<'a> {
             Block::End { event, .. } => event,
         }
     }
+}
 
-    pub fn is_begin(&self) -> bool {
-        match self {
-            Block::Begin { .. } => true,
-            _ => false,
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlockIssue {
+    MissingEnd,
+    MissingBegin,
+    // a block opened before the previous one was closed
+    Nested,
+    NoCommercials,
+    // a siEvent starts during the block
+    CrossesSiEvent,
+}
+
+pub struct BlockError<'a> {
+    pub issue: BlockIssue,
+    pub block: Block<'a>,
+    // the outer block for Nested, the end of the block otherwise
+    pub other: Option<Block<'a>>,
+    pub si: Option<&'a Event>,
+}
+
+type Pairs<'a> = Vec<(Block<'a>, Block<'a>)>;
+
+// Pairs every end marker with the last open begin marker. The indices are
+// the positions of the DEFINEs in the file. A block holding another block is
+// reported once its own end is found, only the inner one is paired. A block
+// that is never closed is only missing its end.
+fn pair<'a>(defines: &'a [Define], rules: &BlockRules) -> (Pairs<'a>, Vec<BlockError<'a>>) {
+    let mut errors = Vec::new();
+    let mut pairs = Vec::new();
+    // the open blocks and the blocks closed inside them
+    let mut stack: Vec<(Block, Vec<Block>)> = Vec::new();
+    for (index, event) in defines.iter().enumerate() {
+        if let Define::siEvent(..) = event {
+            continue;
         }
+        let contentid = event.get_event().get_contentid();
+        if rules.is_begin(&contentid) {
+            stack.push((Block::Begin { index, event }, Vec::new()));
+        } else if rules.is_end(&contentid) {
+            let end = Block::End { index, event };
+            let Some((begin, inner)) = stack.pop() else {
+                errors.push(BlockError {
+                    issue: BlockIssue::MissingBegin,
+                    block: end,
+                    other: None,
+                    si: None,
+                });
+                continue;
+            };
+            let outer = !inner.is_empty();
+            for block in inner {
+                errors.push(BlockError {
+                    issue: BlockIssue::Nested,
+                    block,
+                    other: Some(begin.clone()),
+                    si: None,
+                });
+            }
+            if let Some((_, inner)) = stack.last_mut() {
+                inner.push(begin.clone());
+            }
+            if !outer {
+                pairs.push((begin, end));
+            }
+        }
+    }
+    for (begin, _) in stack {
+        errors.push(BlockError {
+            issue: BlockIssue::MissingEnd,
+            block: begin,
+            other: None,
+            si: None,
+        });
     }
+    (pairs, errors)
+}
 
-    pub fn is_end(&self) -> bool {
-        match self {
-            Block::Begin { .. } => false,
-            _ => true,
+// the first and last DEFINE of every block
+pub fn ranges(defines: &[Define], rules: &BlockRules) -> Vec<(usize, usize)> {
+    pair(defines, rules)
+        .0
+        .iter()
+        .map(|(begin, end)| (begin.index(), end.index()))
+        .collect()
+}
+
+pub fn parse<'a>(
+    defines: &'a [Define],
+    rules: &BlockRules,
+) -> (Vec<SpecialEvent<'a>>, Vec<BlockError<'a>>) {
+    let (pairs, mut errors) = pair(defines, rules);
+
+    let programmes = programmes(defines);
+    let mut result = Vec::new();
+    for (begin, end) in pairs {
+        let special_event = SpecialEvent::new(
+            defines[begin.index()..=end.index()]
+                .iter()
+                .filter(|x| !matches!(x, Define::siEvent(..)))
+                .collect(),
+        );
+        if special_event.get_commercial_events().is_empty() {
+            errors.push(BlockError {
+                issue: BlockIssue::NoCommercials,
+                block: begin.clone(),
+                other: Some(end.clone()),
+                si: None,
+            });
         }
+        if let Some((start, stop)) = special_event.get_span() {
+            for (si, s) in &programmes {
+                if start < s.0 && s.0 < stop {
+                    errors.push(BlockError {
+                        issue: BlockIssue::CrossesSiEvent,
+                        block: begin.clone(),
+                        other: Some(end.clone()),
+                        si: Some(si),
+                    });
+                }
+            }
+        }
+        result.push(special_event);
+    }
+    errors.sort_by_key(|error| error.block.index());
+    (result, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    const BEGIN: &str = "3ecea6b8084f35c2634d";
+    const END: &str = "02e9d82071c7735de5cd";
+
+    fn issues(pts: &Pts) -> Vec<(BlockIssue, usize, Option<usize>)> {
+        let defines = pts.defines();
+        parse(&defines, &BlockRules::default_rules())
+            .1
+            .iter()
+            .map(|error| {
+                let other = error.other.as_ref().map(Block::index);
+                (error.issue, error.block.index(), other)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn block_with_commercials() {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(20))
+            .break_at("18:20:00", minutes(4))
+            .film(2, "18:24:20", minutes(20));
+        let defines = pts.defines();
+        let rules = BlockRules::default_rules();
+        assert_eq!(ranges(&defines, &rules), [(1, 3)]);
+        let (special_events, errors) = parse(&defines, &rules);
+        assert_eq!(special_events.len(), 1);
+        assert_eq!(special_events[0].get_commercial_events().len(), 1);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn orphans_and_nested_blocks() {
+        let mut pts = Pts::new();
+        pts.va("Bald weiter", END, "18:00:00", 10_000, "E0")
+            .va("Dranbleiben", BEGIN, "18:00:10", 10_000, "D1")
+            .va("Trenner", "cb7a119f84cb7b117b1b", "18:00:20", 10_000, "D2")
+            .va("Spot", "UHD1_WERBUNG-01", "18:00:30", minutes(4), "W1")
+            .va("Ende", "392654926764849cd5dc", "18:04:30", 10_000, "E2")
+            .va("Bald weiter", END, "18:04:40", 10_000, "E1")
+            .va("Dranbleiben", BEGIN, "18:04:50", 10_000, "D3");
+        assert_eq!(
+            issues(&pts),
+            [
+                (BlockIssue::MissingBegin, 0, None),
+                (BlockIssue::Nested, 2, Some(1)),
+                (BlockIssue::MissingEnd, 6, None),
+            ]
+        );
+        // only the inner block is one
+        let defines = pts.defines();
+        assert_eq!(ranges(&defines, &BlockRules::default_rules()), [(2, 4)]);
+
+        // an outer block that is never closed is not reported as nested
+        let mut pts = Pts::new();
+        pts.va("Dranbleiben", BEGIN, "18:00:00", 10_000, "D0")
+            .va("Dranbleiben", BEGIN, "18:00:10", 10_000, "D1")
+            .va("Spot", "UHD1_WERBUNG-01", "18:00:20", minutes(4), "W1")
+            .va("Bald weiter", END, "18:04:20", 10_000, "E1");
+        assert_eq!(issues(&pts), [(BlockIssue::MissingEnd, 0, None)]);
+        let defines = pts.defines();
+        assert_eq!(ranges(&defines, &BlockRules::default_rules()), [(1, 3)]);
+    }
+
+    #[test]
+    fn block_without_commercials_across_an_si_event() {
+        let mut pts = Pts::new();
+        pts.va("Dranbleiben", BEGIN, "18:00:00", 10_000, "D1")
+            .va(
+                "Trailer Serie",
+                "6000000-000001",
+                "18:00:10",
+                minutes(1),
+                "T1",
+            )
+            .si("Film", "18:00:30", minutes(90), "P100")
+            .va("Bald weiter", END, "18:01:10", 10_000, "E1");
+        assert_eq!(
+            issues(&pts),
+            [
+                (BlockIssue::NoCommercials, 0, Some(3)),
+                (BlockIssue::CrossesSiEvent, 0, Some(3)),
+            ]
+        );
     }
 }
@@ -0,0 +1,257 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::{merge, programme_at, programmes, span, uncovered, Span};
+use crate::utils::compliance_rules::{ComplianceRules, Coverage};
+use crate::utils::messages::Msg;
+use crate::Fluid;
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ComplianceIssue {
+    Uncovered,
+    MissingAtStart,
+    MissingAtEnd,
+}
+
+impl ComplianceIssue {
+    pub fn msg(self) -> Msg {
+        match self {
+            ComplianceIssue::Uncovered => Msg::ComplianceUncovered,
+            ComplianceIssue::MissingAtStart => Msg::ComplianceMissingAtStart,
+            ComplianceIssue::MissingAtEnd => Msg::ComplianceMissingAtEnd,
+        }
+    }
+}
+
+pub struct ComplianceError<'a> {
+    pub rule: String,
+    pub layout: String,
+    pub issue: ComplianceIssue,
+    pub event: &'a Event,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ComplianceError<'_> {
+    pub fn duration(&self) -> i64 {
+        (self.end - self.start).num_milliseconds()
+    }
+}
+
+// how long the layout is missing right after `time`
+fn missing_after(time: DateTime<Utc>, until: DateTime<Utc>, layouts: &[Span]) -> Option<Span> {
+    uncovered((time, until), layouts)
+        .into_iter()
+        .next()
+        .filter(|(start, _)| *start == time)
+}
+
+// how long the layout is missing right before `time`
+fn missing_before(from: DateTime<Utc>, time: DateTime<Utc>, layouts: &[Span]) -> Option<Span> {
+    uncovered((from, time), layouts)
+        .into_iter()
+        .last()
+        .filter(|(_, end)| *end == time)
+}
+
+pub fn check<'a>(
+    defines: &'a [Define],
+    rules: &ComplianceRules,
+    fluid: &Fluid,
+) -> Vec<ComplianceError<'a>> {
+    let programmes = programmes(defines);
+    let mut errors = Vec::new();
+
+    for rule in &rules.rules {
+        let layouts = merge(
+            defines
+                .iter()
+                .filter_map(|define| match define {
+                    Define::layoutEvent(event) if event.get_contentid() == rule.layout => {
+                        span(event)
+                    }
+                    _ => None,
+                })
+                .collect(),
+        );
+        let affected: Vec<(&Event, Span)> = defines
+            .iter()
+            .filter_map(|define| match define {
+                Define::vaEvent(event) if rule.affects(event, fluid) => {
+                    span(event).map(|s| (event, s))
+                }
+                _ => None,
+            })
+            .collect();
+        let mut push = |issue, event, (start, end): Span| {
+            errors.push(ComplianceError {
+                rule: rule.name.clone(),
+                layout: rule.layout.clone(),
+                issue,
+                event,
+                start,
+                end,
+            })
+        };
+
+        match rule.coverage {
+            Coverage::Whole => {
+                for (event, s) in &affected {
+                    for missing in uncovered(*s, &layouts) {
+                        push(ComplianceIssue::Uncovered, *event, missing);
+                    }
+                }
+            }
+            Coverage::Points => {
+                // contiguous parts between the breaks
+                let mut stretches: Vec<(&Event, Span)> = Vec::new();
+                for (event, s) in affected {
+                    match stretches.last_mut() {
+                        Some((_, last)) if last.1 == s.0 => last.1 = s.1,
+                        _ => stretches.push((event, s)),
+                    }
+                }
+                for (i, (event, stretch)) in stretches.iter().enumerate() {
+                    let at_start = missing_after(stretch.0, stretch.1, &layouts);
+                    if let Some(missing) = at_start {
+                        push(ComplianceIssue::MissingAtStart, *event, missing);
+                    }
+                    // the end of the programme, the next part belongs to another one
+                    let last = match stretches.get(i + 1) {
+                        None => true,
+                        Some((_, next)) => match (
+                            programme_at(&programmes, stretch.0),
+                            programme_at(&programmes, next.0),
+                        ) {
+                            (Some(a), Some(b)) => !std::ptr::eq(a, b),
+                            _ => true,
+                        },
+                    };
+                    let at_end = missing_before(stretch.0, stretch.1, &layouts);
+                    if let Some(missing) = at_end.filter(|_| last && at_end != at_start) {
+                        push(ComplianceIssue::MissingAtEnd, *event, missing);
+                    }
+                }
+            }
+        }
+    }
+    errors.sort_by_key(|error| error.start);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{at, minutes, Pts};
+
+    fn issues(pts: &Pts, rules: &ComplianceRules, fluid: &Fluid) -> Vec<(String, Span)> {
+        let defines = pts.defines();
+        check(&defines, rules, fluid)
+            .iter()
+            .map(|error| {
+                let issue = match error.issue {
+                    ComplianceIssue::Uncovered => "uncovered",
+                    ComplianceIssue::MissingAtStart => "start",
+                    ComplianceIssue::MissingAtEnd => "end",
+                };
+                (
+                    format!("{} {}", error.rule, issue),
+                    (error.start, error.end),
+                )
+            })
+            .collect()
+    }
+
+    // a product placement film in two segments with a break between them
+    fn film(layouts: &[(&str, i64)]) -> Pts {
+        let mut pts = Pts::new();
+        pts.si("Film", "18:00:00", minutes(50), "P100")
+            .film(1, "18:00:00", minutes(20))
+            .va(
+                "Spot UHD1_WERBUNG-01",
+                "UHD1_WERBUNG-01",
+                "18:20:00",
+                minutes(10),
+                "W1",
+            )
+            .film(2, "18:30:00", minutes(20));
+        for (start, ms) in layouts {
+            pts.layout("HDPLUHD_LAY_5", start, *ms, "P100");
+        }
+        pts
+    }
+
+    fn placement() -> Fluid {
+        Fluid::from_entries(&[&[("ContentId", "1572515"), ("Class", " pp ")]])
+    }
+
+    #[test]
+    fn placement_marked_at_start_after_the_break_and_at_the_end() {
+        let pts = film(&[
+            ("18:00:00", 10_000),
+            ("18:30:00", 10_000),
+            ("18:49:50", 10_000),
+        ]);
+        let rules = ComplianceRules::default_rules();
+        assert!(issues(&pts, &rules, &placement()).is_empty());
+    }
+
+    #[test]
+    fn placement_missing_after_the_break_and_at_the_end() {
+        let pts = film(&[("18:00:00", 10_000)]);
+        let rules = ComplianceRules::default_rules();
+        assert_eq!(
+            issues(&pts, &rules, &placement()),
+            [(
+                "Produktplatzierung start".to_string(),
+                (at("18:30:00"), at("18:50:00"))
+            )]
+        );
+
+        let pts = film(&[("18:00:00", 10_000), ("18:30:00", 10_000)]);
+        assert_eq!(
+            issues(&pts, &rules, &placement()),
+            [(
+                "Produktplatzierung end".to_string(),
+                (at("18:30:10"), at("18:50:00"))
+            )]
+        );
+    }
+
+    #[test]
+    fn unclassified_material_needs_no_layout() {
+        let pts = film(&[]);
+        let rules = ComplianceRules::default_rules();
+        assert!(issues(&pts, &rules, &Fluid::init()).is_empty());
+    }
+
+    #[test]
+    fn infomercial_covered_for_its_whole_duration() {
+        let rules: ComplianceRules = serde_json::from_str(
+            r#"{"rules": [{
+                "name": "Dauerwerbesendung",
+                "layout": "HDPLUHD_LAY_4",
+                "coverage": "whole",
+                "content_ids": ["DWS-"]
+            }]}"#,
+        )
+        .unwrap();
+        let mut pts = Pts::new();
+        pts.va("Shopping", "DWS-4711", "18:00:00", minutes(30), "P200")
+            .layout("HDPLUHD_LAY_4", "18:05:00", minutes(10), "P200")
+            .layout("HDPLUHD_LAY_4", "18:15:00", minutes(10), "P200");
+        assert_eq!(
+            issues(&pts, &rules, &Fluid::init()),
+            [
+                (
+                    "Dauerwerbesendung uncovered".to_string(),
+                    (at("18:00:00"), at("18:05:00"))
+                ),
+                (
+                    "Dauerwerbesendung uncovered".to_string(),
+                    (at("18:25:00"), at("18:30:00"))
+                ),
+            ]
+        );
+    }
+}
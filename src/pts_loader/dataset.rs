@@ -1,25 +1,63 @@
 use super::{define::*, special_event};
-use crate::commandline::commandline::Commandline;
+use crate::commandline::commandline::{Commandline, FixStrategy, Range};
 use crate::commandline::summary::Summary;
-use crate::pts_loader::block::Block;
+use crate::pts_loader::advertising;
+use crate::pts_loader::block::{self, BlockError};
+use crate::pts_loader::compliance;
+use crate::pts_loader::day_coverage;
+use crate::pts_loader::epg_text;
 use crate::pts_loader::event::Event;
+use crate::pts_loader::identifiers;
+use crate::pts_loader::interruptions::{self, InterruptionIssue};
+use crate::pts_loader::logo_coverage::{self, LogoIssue};
+use crate::pts_loader::material_bounds::{self, OffsetIssue};
+use crate::pts_loader::material_status;
+use crate::pts_loader::reruns::{self, Airing};
+use crate::pts_loader::sendepause;
+use crate::pts_loader::si_fix;
 use crate::pts_loader::sistandard::a_duration_from_string;
 use crate::pts_loader::special_event::SpecialEvent;
+use crate::pts_loader::titles::{self, TitleIssue};
+use crate::pts_loader::trailer_balls::{self, BallIssue};
+use crate::pts_loader::video_format::{self, FormatIssue};
+use crate::pts_loader::youth_protection;
+use crate::utils::ball_rules::BallRules;
+use crate::utils::block_rules::BlockRules;
+use crate::utils::compliance_rules::ComplianceRules;
+use crate::utils::encoding;
+use crate::utils::epg_rules::EpgRules;
 use crate::utils::excel::Excel;
 use crate::utils::fluid::QueryType;
-use crate::utils::table_print::{self, print_line};
-use crate::utils::take::Take;
+use crate::utils::format_rules::FormatRules;
+use crate::utils::layout_rules::{Conflict, LayoutRules};
+use crate::utils::messages::Msg;
+use crate::utils::plan::{self, Deviation, Plan, PlanBreak};
+use crate::utils::rerun_rules::RerunRules;
+use crate::utils::sendepause_rules::SendepauseRules;
+use crate::utils::source;
+use crate::utils::table::{Column, Table};
+use crate::utils::youth_rules::YouthRules;
 use crate::Fluid;
-use colored::Colorize;
+use chrono::{
+    DateTime, Duration, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone,
+    Timelike, Utc,
+};
+use colored::{ColoredString, Colorize};
 use rust_xlsxwriter::XlsxError;
 use serde::{Deserialize, Serialize};
-use std::fs::{self, File};
+use std::fs::File;
 use std::io::prelude::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct DataSet {
     filename: Option<String>,
 
+    #[serde(skip)]
+    entry: Option<String>,
+
+    #[serde(skip)]
+    block_rules: BlockRules,
+
     #[serde(rename = "eventCommands")]
     eventcommands: EventCommands,
 }
@@ -30,8 +68,118 @@ pub struct EventCommands {
     define: Vec<Define>,
 }
 
-fn load_file(filename: &str) -> std::io::Result<String> {
-    fs::read_to_string(filename)
+fn compliance_header() -> Vec<String> {
+    vec![
+        Msg::ColRule.to_string(),
+        Msg::ColTitle.to_string(),
+        Msg::ColProgramId.to_string(),
+        Msg::ColIssue.to_string(),
+        Msg::ColStart.to_string(),
+        Msg::ColEnd.to_string(),
+        Msg::ColUncovered.to_string(),
+    ]
+}
+
+fn advertising_header() -> Vec<String> {
+    vec![
+        Msg::ColStart.to_string(),
+        Msg::ColEnd.to_string(),
+        Msg::ColAdvertising.to_string(),
+        Msg::ColLimit.to_string(),
+        Msg::ColShare.to_string(),
+    ]
+}
+
+fn ball_header() -> Vec<String> {
+    vec![
+        Msg::ColTitle.to_string(),
+        Msg::ColStart.to_string(),
+        Msg::ColIssue.to_string(),
+        Msg::ColNeighbour.to_string(),
+        Msg::ColDuration.to_string(),
+        Msg::ColType.to_string(),
+        Msg::ColExpected.to_string(),
+    ]
+}
+
+fn material_header() -> Vec<String> {
+    vec![
+        Msg::ColTitle.to_string(),
+        Msg::ColStart.to_string(),
+        Msg::ColProgramId.to_string(),
+        Msg::ColContentId.to_string(),
+        Msg::ColMaterialId.to_string(),
+        Msg::ColFilename.to_string(),
+        Msg::ColIssue.to_string(),
+    ]
+}
+
+// a planned break and a block may start this far apart and still be paired
+const PLAN_START_TOLERANCE: i64 = 30 * 60_000;
+
+pub fn local_time(time: DateTime<Utc>, utc: bool) -> NaiveDateTime {
+    if utc {
+        time.naive_utc()
+    } else {
+        DateTime::<Local>::from(time).naive_local()
+    }
+}
+
+// the inverse of local_time, the earlier one of a time that occurs twice and
+// the moment the clocks go forward for a time they skip
+pub fn utc_time(time: NaiveDateTime, utc: bool) -> DateTime<Utc> {
+    if utc {
+        return Utc.from_utc_datetime(&time);
+    }
+    // chrono does not return the two times of the night the clocks go back
+    // in order, and it returns 03:00 twice although it only happens once
+    match Local.from_local_datetime(&time) {
+        LocalResult::Single(t) => t.with_timezone(&Utc),
+        LocalResult::Ambiguous(a, b) => [a, b]
+            .into_iter()
+            .filter(|t| t.with_timezone(&Local).naive_local() == time)
+            .min()
+            .unwrap_or(a.min(b))
+            .with_timezone(&Utc),
+        // the clocks go forward on the hour
+        LocalResult::None => {
+            let hour = time.date().and_time(NaiveTime::MIN) + Duration::hours(time.hour() as i64);
+            utc_time(hour - Duration::seconds(1), utc) + Duration::seconds(1)
+        }
+    }
+}
+
+// the broadcast day `time` belongs to, a day starting at 06:00 ends at 06:00
+// of the next calendar day
+pub fn broadcast_day(time: DateTime<Utc>, day_start: u32, utc: bool) -> NaiveDate {
+    (local_time(time, utc) - Duration::hours(day_start as i64)).date()
+}
+
+// start and end of a broadcast day in utc, a day can have 23 or 25 hours
+pub fn day_bounds(day: NaiveDate, day_start: u32, utc: bool) -> (DateTime<Utc>, DateTime<Utc>) {
+    let at = |date: NaiveDate| {
+        utc_time(date.and_time(NaiveTime::MIN) + Duration::hours(day_start as i64), utc)
+    };
+    (at(day), at(day + Duration::days(1)))
+}
+
+fn export_header(cmd: &Commandline) -> Vec<String> {
+    let fps = match cmd.fps() {
+        Some(25) => "25fps",
+        Some(50) => "50fps",
+        _ => "ms",
+    };
+    vec![
+        Msg::ColTitle.to_string(),
+        Msg::ColFilename.to_string(),
+        format!("{} ({fps})", Msg::ColStart),
+        format!("{} ({fps})", Msg::ColEnd),
+        format!("{} ({fps})", Msg::ColDuration),
+        format!("{} ({fps})", Msg::ColTcin),
+        format!("{} ({fps})", Msg::ColTcout),
+        Msg::ColContentId.to_string(),
+        Msg::ColLogo.to_string(),
+    ]
 }
 
 impl DataSet {
@@ -61,79 +209,368 @@ impl DataSet {
             match fluid_data_set.query(&event, QueryType::Duration) {
                 None => (),
                 Some(duration) => {
-                    let event_duration: i64 = event.get_duration();
+                    // a segment starting 20 minutes into the material has 20
+                    // minutes less to run
+                    let (tcin, tcout) = event
+                        .get_tcin_tcout()
+                        .unwrap_or((0, event.get_duration()));
                     let dbase_duration: i64 = match a_duration_from_string(duration) {
                         Ok(i) => i,
                         Err(..) => 0,
                     };
-                    if event_duration > dbase_duration {
-                        content_length_errors.push((event, dbase_duration));
+                    if tcout > dbase_duration {
+                        content_length_errors.push((event, tcin, tcout, dbase_duration));
                     }
                 }
             }
         }
 
         if content_length_errors.len() > 0 {
-            let length = 182;
-            println!(
-                "\n{}:\n|{}|",
-                "Content length error".to_string().red(),
-                "-".repeat(length)
-            );
-            println!(
-                "| {} | {} | {} | {} | {} | {} |",
-                "title".to_string().take(40),
-                "starttime".to_string().take(30),
-                "programid".to_string().take(25),
-                "id".to_string().take(20),
-                "duration".to_string().take(20),
-                "database duration".to_string().take(30)
-            );
-            println!("|{}|", "-".repeat(length));
-            for (event, dbase_duration) in content_length_errors.iter() {
-                println!(
-                    "| {} | {} | {} | {} | {} | {} |",
-                    event.get_title().to_string().take(40),
-                    event.starttime_to_string(cmd.utc(), cmd.fps()).take(30),
-                    event.get_programid().to_string().take(25),
-                    event.get_contentid().to_string().take(20),
-                    Event::a_duration_to_string(event.get_duration(), cmd.fps())
-                        .to_string()
-                        .take(20)
-                        .red(),
-                    Event::a_duration_to_string(*dbase_duration, cmd.fps())
-                        .to_string()
-                        .take(30)
-                );
-                println!("|{}|", "-".repeat(length));
+            println!("\n{}:", Msg::ContentLengthError.text().red());
+            let mut table = Table::new(vec![
+                Column::new("title", Msg::ColTitle.text(), 40),
+                Column::new("start", Msg::ColStart.text(), 30),
+                Column::new("programid", Msg::ColProgramId.text(), 25),
+                Column::new("contentid", Msg::ColContentId.text(), 20),
+                Column::new("duration", Msg::ColDuration.text(), 20),
+                Column::new("tcin", Msg::ColTcin.text(), 12),
+                Column::new("tcout", Msg::ColTcout.text(), 12),
+                Column::new("fluid_duration", Msg::ColFluidDuration.text(), 30),
+                Column::new("overrun", Msg::ColOverrun.text(), 12),
+            ]);
+            if let Some(columns) = cmd.columns() {
+                table.select(&columns);
             }
+            for (event, tcin, tcout, dbase_duration) in content_length_errors.iter() {
+                table.add_row(vec![
+                    event.get_title().normal(),
+                    event.starttime_to_string(cmd.utc(), cmd.fps()).normal(),
+                    event.get_programid().normal(),
+                    event.get_contentid().normal(),
+                    Event::a_duration_to_string(event.get_duration(), cmd.fps()).normal(),
+                    Event::a_duration_to_string(*tcin, cmd.fps()).normal(),
+                    Event::a_duration_to_string(*tcout, cmd.fps()).red(),
+                    Event::a_duration_to_string(*dbase_duration, cmd.fps()).normal(),
+                    Event::a_duration_to_string(tcout - dbase_duration, cmd.fps()).red(),
+                ]);
+                table.add_separator();
+            }
+            table.print(cmd);
             println!("");
         }
         summary.content_to_long_error += content_length_errors.len();
     }
 
+    pub fn list_segment_offset_errors(&self, summary: &mut Summary, cmd: &Commandline) {
+        let errors = material_bounds::check_offsets(&self.eventcommands.define);
+        summary.offset_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let tc = |event: &Event| match event.get_tcin_tcout() {
+            Some((tcin, tcout)) => format!(
+                "{} - {}",
+                Event::a_duration_to_string(tcin, cmd.fps()),
+                Event::a_duration_to_string(tcout, cmd.fps())
+            ),
+            None => String::new(),
+        };
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("offsets", Msg::ColOffsets.text(), 27),
+            Column::new("previous", Msg::ColPrevious.text(), 27),
+            Column::new("issue", Msg::ColIssue.text(), 30),
+            Column::new("overlap", Msg::ColOverlap.text(), 12),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let issue = match error.issue {
+                OffsetIssue::Overlap => Msg::OffsetOverlap,
+                OffsetIssue::OutOfOrder => Msg::OffsetOutOfOrder,
+            };
+            table.add_row(vec![
+                error.event.get_title().normal(),
+                error.event.get_programid().normal(),
+                error
+                    .event
+                    .starttime_to_string(cmd.utc(), cmd.fps())
+                    .normal(),
+                tc(error.event).normal(),
+                tc(error.previous).normal(),
+                issue.text().red(),
+                Event::a_duration_to_string(error.overlap, cmd.fps()).normal(),
+            ]);
+        }
+        println!("{}", Msg::OffsetErrorsHeading.text().red());
+        table.print(cmd);
+    }
+
+    // one row per vaEvent whose material is unknown, binned or unregistered
+    fn material_rows(&self, cmd: &Commandline, fluid: &Fluid) -> Vec<Vec<String>> {
+        material_status::check(&self.eventcommands.define, &self.block_rules, fluid, cmd)
+            .into_iter()
+            .map(|error| {
+                vec![
+                    error.event.get_title(),
+                    error.event.starttime_to_string(cmd.utc(), cmd.fps()),
+                    error.event.get_programid(),
+                    error.event.get_contentid(),
+                    error.material_id,
+                    error.filename,
+                    error.issue.msg().to_string(),
+                ]
+            })
+            .collect()
+    }
+
+    pub fn check_material_status(&self, summary: &mut Summary, cmd: &Commandline, fluid: &Fluid) {
+        // an empty or unreadable fluid export would make all material unknown
+        if fluid.size() == 0 {
+            return;
+        }
+        let rows = self.material_rows(cmd, fluid);
+        summary.material_error += rows.len() as i64;
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("contentid", Msg::ColContentId.text(), 20),
+            Column::new("materialid", Msg::ColMaterialId.text(), 15),
+            Column::new("filename", Msg::ColFilename.text(), 25),
+            Column::new("issue", Msg::ColIssue.text(), 25),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for row in rows {
+            let mut cells: Vec<ColoredString> = row.into_iter().map(|cell| cell.normal()).collect();
+            cells[6] = cells[6].clone().red();
+            table.add_row(cells);
+        }
+        println!("{}", Msg::MaterialErrorsHeading.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_video_format(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        rules: &FormatRules,
+        fluid: &Fluid,
+    ) {
+        let errors = video_format::check(&self.eventcommands.define, rules, fluid);
+        summary.format_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("serviceid", Msg::ColServiceId.text(), 10),
+            Column::new("contentid", Msg::ColContentId.text(), 20),
+            Column::new("filename", Msg::ColFilename.text(), 25),
+            Column::new("issue", Msg::ColIssue.text(), 25),
+            Column::new("value", Msg::ColValue.text(), 10),
+            Column::new("allowed", Msg::ColAllowed.text(), 25),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let issue = match error.issue {
+                FormatIssue::Resolution => Msg::FormatResolution,
+                FormatIssue::Format => Msg::FormatFormat,
+            };
+            table.add_row(vec![
+                error.event.get_title().normal(),
+                error
+                    .event
+                    .starttime_to_string(cmd.utc(), cmd.fps())
+                    .normal(),
+                error.event.get_serviceid().normal(),
+                error.event.get_contentid().normal(),
+                error.filename.normal(),
+                issue.text().red(),
+                error.value.red(),
+                error.allowed.join(", ").normal(),
+            ]);
+        }
+        println!("{}", Msg::FormatErrorsHeading.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_epg_text(&self, summary: &mut Summary, cmd: &Commandline, rules: &EpgRules) {
+        let errors = epg_text::check(&self.eventcommands.define, rules);
+        summary.epg_text_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("field", Msg::ColField.text(), 16),
+            Column::new("issue", Msg::ColIssue.text(), 26),
+            Column::new("detail", Msg::ColDetail.text(), 20),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            table.add_row(vec![
+                error.event.get_title().normal(),
+                error.event.get_programid().normal(),
+                error
+                    .event
+                    .starttime_to_string(cmd.utc(), cmd.fps())
+                    .normal(),
+                error.field.normal(),
+                error.issue.msg().text().red(),
+                error.detail.normal(),
+            ]);
+        }
+        println!("{}", Msg::EpgTextHeading.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_titles(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        pauses: &SendepauseRules,
+        balls: &BallRules,
+    ) {
+        let (special_events, _) = self.get_special_events();
+        let errors = titles::check(
+            &self.eventcommands.define,
+            &special_events,
+            pauses,
+            balls,
+            cmd.title_similarity(),
+        );
+        summary.title_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("epgtitle", Msg::ColEpgTitle.text(), 30),
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("issue", Msg::ColIssue.text(), 20),
+            Column::new("similarity", Msg::ColSimilarity.text(), 10),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let (issue, event) = match error.issue {
+                TitleIssue::EpgMismatch => (Msg::TitleEpgMismatch, error.si),
+                TitleIssue::NotInEpg => (Msg::TitleNotInEpg, error.va),
+            };
+            let title = |event: Option<&Event>| event.map(|x| x.get_title()).unwrap_or_default();
+            table.add_row(vec![
+                title(error.si).normal(),
+                title(error.va).normal(),
+                event.map(|x| x.get_programid()).unwrap_or_default().normal(),
+                event
+                    .map(|x| x.starttime_to_string(cmd.utc(), cmd.fps()))
+                    .unwrap_or_default()
+                    .normal(),
+                issue.text().red(),
+                format!("{:.0}%", error.similarity * 100.0).normal(),
+            ]);
+        }
+        println!("{}", Msg::TitleErrorsHeading.text().red());
+        table.print(cmd);
+    }
+
+    #[cfg(test)]
+    pub fn defines(&self) -> &[Define] {
+        &self.eventcommands.define
+    }
+
     pub fn init_from_data(xml_text: String) -> Result<DataSet, serde_xml_rs::Error> {
-        let mut dataset: DataSet = serde_xml_rs::from_str(&xml_text)?;
+        let mut dataset: DataSet = serde_xml_rs::from_str(&encoding::declare_utf8(&xml_text))?;
         dataset.calculate_endtimes();
         Ok(dataset)
     }
 
-    pub fn init(filename: &str) -> Result<DataSet, serde_xml_rs::Error> {
-        match load_file(filename) {
-            Err(e) => {
-                let err = serde_xml_rs::Error::Custom {
-                    field: format!("{}", e),
+    // one dataset per schedule, archives can hold a whole week
+    pub fn load(
+        path: &str,
+        entries: Option<&str>,
+        input_encoding: Option<&str>,
+    ) -> std::io::Result<Vec<(String, Result<DataSet, serde_xml_rs::Error>)>> {
+        let sources = source::open(path, entries)?;
+        let archive = sources.iter().any(|s| s.name != path);
+        Ok(sources
+            .into_iter()
+            .map(|source| {
+                let dataset = match encoding::decode(&source.bytes, input_encoding) {
+                    Err(e) => Err(serde_xml_rs::Error::Custom {
+                        field: format!("{}", e),
+                    }),
+                    Ok(decoded) => DataSet::init_from_data(decoded.text).map(|mut dataset| {
+                        dataset.filename = Some(source.name.clone());
+                        if archive {
+                            dataset.entry = source.entry.clone();
+                        }
+                        dataset
+                    }),
                 };
-                Err(err)
+                (source.name, dataset)
+            })
+            .collect())
+    }
+
+    // exports of archive entries get the entry name appended
+    pub fn export_path(&self, path: &str) -> String {
+        source::entry_path(path, self.entry.as_deref())
+    }
+
+    pub fn set_block_rules(&mut self, rules: BlockRules) {
+        self.block_rules = rules;
+    }
+
+    // Keeps the events of one broadcast day. An event belongs to the day it
+    // starts in, the events of a block to the day the block starts in.
+    pub fn select_day(&mut self, day: NaiveDate, day_start: u32, utc: bool) {
+        self.select(|start| broadcast_day(start, day_start, utc) == day);
+    }
+
+    // keeps the events starting in the range, blocks by their start
+    pub fn select_range(&mut self, range: &Range) {
+        self.select(|start| range.contains(start));
+    }
+
+    fn select(&mut self, selected: impl Fn(DateTime<Utc>) -> bool) {
+        let defines = &self.eventcommands.define;
+        let mut starts: Vec<Option<DateTime<Utc>>> = defines
+            .iter()
+            .map(|define| define.get_event().get_starttime())
+            .collect();
+        for (begin, end) in block::ranges(defines, &self.block_rules) {
+            for start in &mut starts[begin..=end] {
+                *start = defines[begin].get_event().get_starttime();
             }
-            Ok(xml_text) => match DataSet::init_from_data(xml_text) {
-                Ok(mut dataset) => {
-                    dataset.filename = Some(filename.to_string());
-                    Ok(dataset)
-                }
-                err => err,
-            },
         }
+        let mut keep = starts
+            .into_iter()
+            .map(|start| start.is_some_and(&selected));
+        self.eventcommands.define.retain(|_| keep.next().unwrap_or(false));
     }
 
     fn calculate_endtimes(&mut self) {
@@ -156,15 +593,16 @@ impl DataSet {
             .collect();
         let length = va_events.len();
         println!(
-            "{:3} illegal vaEvents found",
+            "{:3} {}",
             if length == 0 {
                 format!("{}", length).green()
             } else {
                 format!("{}", length).red()
-            }
+            },
+            Msg::IllegalVaEventsFound
         );
         if cmd.verbose() {
-            println!("{}", "Illegal events:");
+            println!("{}", Msg::IllegalEvents);
             va_events.iter().for_each(|x| println!("{:?}", x));
         } else {
             va_events.iter().for_each(|x| println!("{}", x));
@@ -184,12 +622,13 @@ impl DataSet {
             .collect();
         if si_events.len() > 0 {
             println!(
-                "{:3} illegal siEvents found",
+                "{:3} {}",
                 if 0 == si_events.len() {
                     format!("{}", si_events.len()).green()
                 } else {
                     format!("{}", si_events.len()).red()
-                }
+                },
+                Msg::IllegalSiEventsFound
             );
             if cmd.verbose() {
                 si_events.iter().for_each(|x| println!("{:?}", x));
@@ -211,7 +650,7 @@ impl DataSet {
             })
             .collect();
         if logo_events.len() > 0 {
-            println!("{} illegal logoEvents found", logo_events.len());
+            println!("{} {}", logo_events.len(), Msg::IllegalLogoEventsFound);
             if cmd.verbose() {
                 logo_events.iter().for_each(|x| println!("{:?}", x));
             } else {
@@ -232,7 +671,7 @@ impl DataSet {
             })
             .collect();
         if layout_events.len() > 0 {
-            println!("{} illegal layoutEvents found", layout_events.len());
+            println!("{} {}", layout_events.len(), Msg::IllegalLayoutEventsFound);
             if cmd.verbose() {
                 layout_events.iter().for_each(|x| println!("{:?}", x));
             } else {
@@ -257,7 +696,7 @@ impl DataSet {
             .collect();
 
         if events.len() == 0 {
-            println!("{:3} illegal events found.", format!("0").green());
+            println!("{:3} {}", format!("0").green(), Msg::IllegalEventsFound);
         } else {
             self.look_for_illegals_va_events(events, cmd);
             self.look_for_illegals_logo_events(events, cmd);
@@ -277,7 +716,7 @@ impl DataSet {
         event.print_si_events_verbose(next_event, &err, &display_err, verbose, utc);
     }
 
-    fn get_si_events(&self) -> SiEvents {
+    fn get_si_events(&self) -> SiEvents<'_> {
         SiEvents {
             events: self
                 .eventcommands
@@ -399,78 +838,33 @@ impl DataSet {
         }
     }
 
-    fn get_special_events(&self) -> (Vec<SpecialEvent>, Vec<Block<'_>>) {
-        let special_events = self
-            .eventcommands
-            .define
-            .iter()
-            .filter(|x| {
-                if let Define::siEvent(..) = x {
-                    false
-                } else {
-                    true
-                }
-            })
-            .collect::<Vec<&Define>>();
-
-        let blocks: Vec<Block<'_>> = special_events
-            .iter()
-            .enumerate()
-            .filter(|(_, &x)| {
-                let contentid = x.get_event().get_contentid();
-                contentid == "cb7a119f84cb7b117b1b"
-                    || contentid == "392654926764849cd5dc"
-                    || contentid == "3ecea6b8084f35c2634d"
-                    || contentid == "02e9d82071c7735de5cd"
-            })
-            .map(|(i, x)| {
-                let contentid = x.get_event().get_contentid();
-                if contentid == "cb7a119f84cb7b117b1b" || contentid == "3ecea6b8084f35c2634d" {
-                    Block::Begin {
-                        index: i as usize,
-                        event: x,
-                    }
-                } else {
-                    Block::End {
-                        index: i as usize,
-                        event: x,
-                    }
-                }
-            })
-            .collect();
-        let mut special_event_errors = Vec::new();
-        let mut pairs = Vec::new();
-        for (i, block) in blocks.iter().enumerate() {
-            if block.is_begin() && i + 1 < blocks.len() && blocks[i + 1].is_end() {
-                pairs.push((block, &blocks[i + 1]));
-            } else if block.is_begin() {
-                special_event_errors.push(block.clone());
-            } else if block.is_end() && i == 0 {
-                special_event_errors.push(block.clone());
-            }
-        }
-
-        let mut result = Vec::new();
-        for vec in &pairs {
-            let (begin, end) = vec;
-            result.push(SpecialEvent::new(
-                special_events[begin.index()..=end.index()].to_vec(),
-            ));
-        }
-
-        (result, special_event_errors)
+    fn get_special_events(&self) -> (Vec<SpecialEvent<'_>>, Vec<BlockError<'_>>) {
+        block::parse(&self.eventcommands.define, &self.block_rules)
     }
 
     pub fn update_commercials(&self, cmd: &Commandline) -> std::io::Result<String> {
-        let new_filename = format!("{}", cmd.filename().replace(".\\", ""));
         let (special_events, _) = &self.get_special_events();
         let commercials_liste: Vec<_> =
             special_events.iter().map(|e| e.get_commercials()).collect();
+        let mut new_filename = cmd.filename().replace(".\\", "");
         if !commercials_liste.is_empty() {
-            let mut source = File::open(cmd.filename())?;
-            let mut data = String::new();
-            source.read_to_string(&mut data)?;
-            drop(source);
+            if cmd.filename() == source::STDIN {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    Msg::CannotUpdateStdin.to_string(),
+                ));
+            }
+            let source = source::open(cmd.filename(), cmd.entries())?
+                .into_iter()
+                .find(|source| Some(&source.name) == self.filename.as_ref())
+                .ok_or(std::io::ErrorKind::NotFound)?;
+            // entries of archives are written next to the archive
+            new_filename = match &source.entry {
+                None => cmd.filename().replace(".\\", ""),
+                Some(entry) => source::beside(cmd.filename(), entry),
+            };
+            let decoded = encoding::decode(&source.bytes, cmd.input_encoding())?;
+            let mut data = decoded.text.clone();
 
             for commercials in commercials_liste {
                 if !commercials.is_empty() {
@@ -498,28 +892,108 @@ impl DataSet {
                 }
             }
             let mut dest = File::create(&new_filename)?;
-            dest.write(data.as_bytes())?;
+            dest.write(&decoded.encode(&data))?;
             drop(dest);
         }
 
         Ok(new_filename)
     }
 
+    // Prints the corrected siEvent times and writes them into a copy of the
+    // file. Returns the path of the copy, None if nothing was written.
+    pub fn fix_si_events(
+        &self,
+        cmd: &Commandline,
+        strategy: FixStrategy,
+    ) -> std::io::Result<Option<String>> {
+        let (changes, unfixed) = si_fix::plan(&self.eventcommands.define, strategy, cmd);
+        if changes.is_empty() && unfixed.is_empty() {
+            println!("{}", Msg::SiFixNone);
+            return Ok(None);
+        }
+
+        if !changes.is_empty() {
+            let mut table = Table::new(vec![
+                Column::new("title", Msg::ColTitle.text(), 30),
+                Column::new("eventid", Msg::ColEventId.text(), 10),
+                Column::new("field", Msg::ColField.text(), 17),
+                Column::new("old", Msg::ColOld.text(), 26),
+                Column::new("new", Msg::ColNew.text(), 26),
+            ]);
+            if let Some(columns) = cmd.columns() {
+                table.select(&columns);
+            }
+            for change in &changes {
+                table.add_row(vec![
+                    change.event.get_title().normal(),
+                    change.event.get_eventid().normal(),
+                    change.attribute.normal(),
+                    format!("- {}", change.old).red(),
+                    format!("+ {}", change.new).green(),
+                ]);
+            }
+            println!("{}", Msg::SiFixHeading);
+            table.print(cmd);
+        }
+
+        if !unfixed.is_empty() {
+            let mut table = Table::new(vec![
+                Column::new("first", Msg::ColFirst.text(), 30),
+                Column::new("second", Msg::ColSecond.text(), 30),
+                Column::new("start", Msg::ColStart.text(), 23),
+            ]);
+            if let Some(columns) = cmd.columns() {
+                table.select(&columns);
+            }
+            for (first, second) in &unfixed {
+                table.add_row(vec![
+                    first.get_title().normal(),
+                    second.get_title().normal(),
+                    second.starttime_to_string(cmd.utc(), cmd.fps()).red(),
+                ]);
+            }
+            println!("{}", Msg::SiUnfixedHeading.text().red());
+            table.print(cmd);
+        }
+
+        if changes.is_empty() || cmd.fix_dry_run() {
+            return Ok(None);
+        }
+        if cmd.filename() == source::STDIN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                Msg::CannotUpdateStdin.to_string(),
+            ));
+        }
+        let source = source::open(cmd.filename(), cmd.entries())?
+            .into_iter()
+            .find(|source| Some(&source.name) == self.filename.as_ref())
+            .ok_or(std::io::ErrorKind::NotFound)?;
+        let path = match (cmd.fix_output(), &source.entry) {
+            (Some(output), _) => self.export_path(&output),
+            (None, None) => source::entry_path(cmd.filename(), Some("fixed")),
+            (None, Some(entry)) => {
+                source::entry_path(&source::beside(cmd.filename(), entry), Some("fixed"))
+            }
+        };
+        let decoded = encoding::decode(&source.bytes, cmd.input_encoding())?;
+        let data = si_fix::apply(&decoded.text, &changes);
+        File::create(&path)?.write_all(&decoded.encode(&data))?;
+        Ok(Some(path))
+    }
+
     pub fn write_special_events_csv(
         &self,
         cmd: &Commandline,
         fluid_data_set: &Fluid,
+        compliance: Option<&ComplianceRules>,
+        balls: Option<&BallRules>,
     ) -> std::io::Result<()> {
         use std::env;
 
         let (special_events, _errors) = &self.get_special_events();
-        let mut file = File::create(cmd.csv())?;
-        let fps = match cmd.fps() {
-            Some(25) => "25fps",
-            Some(50) => "50fps",
-            _ => "ms",
-        };
-        let head = format!("title;filename;start ({fps});end ({fps});duration ({fps});tcin ({fps});tcout ({fps});contentid;logo;\n");
+        let mut file = File::create(self.export_path(&cmd.csv()))?;
+        let head = format!("{};\n", export_header(cmd).join(";"));
         match file.write_all(head.as_bytes()) {
             _ => (),
         }
@@ -543,10 +1017,59 @@ impl DataSet {
                     Ok(..) => (),
                 }
             } else {
-                println!("only available encodings are utf-8 and windows1252\nNo file written.");
+                println!("{}", Msg::EncodingNotAvailable);
             }
         });
 
+        let mut text = String::new();
+        if let Some(rules) = compliance {
+            let rows = self.compliance_rows(cmd, rules, fluid_data_set);
+            if !rows.is_empty() {
+                text += &format!("\n{};\n", compliance_header().join(";"));
+                for row in rows {
+                    text += &format!("{};\n", row.join(";"));
+                }
+            }
+        }
+        if cmd.all() || cmd.advertising_time() {
+            let rows = self.advertising_rows(cmd);
+            if !rows.is_empty() {
+                text += &format!("\n{};\n", advertising_header().join(";"));
+                for (_, row) in rows {
+                    text += &format!("{};\n", row.join(";"));
+                }
+            }
+        }
+        if let Some(rules) = balls {
+            let rows = self.ball_rows(cmd, rules);
+            if !rows.is_empty() {
+                text += &format!("\n{};\n", ball_header().join(";"));
+                for row in rows {
+                    text += &format!("{};\n", row.join(";"));
+                }
+            }
+        }
+        if (cmd.all() || cmd.material_status()) && fluid_data_set.size() > 0 {
+            let rows = self.material_rows(cmd, fluid_data_set);
+            if !rows.is_empty() {
+                text += &format!("\n{};\n", material_header().join(";"));
+                for row in rows {
+                    text += &format!("{};\n", row.join(";"));
+                }
+            }
+        }
+        if !text.is_empty() {
+            if cmd.encoding() == "utf-8" || cmd.encoding().contains("linux") {
+                file.write_all(text.as_bytes())?;
+            } else if cmd.encoding().contains("1252")
+                || cmd.encoding().contains("win")
+                || env::consts::OS == "windows"
+            {
+                let (windows_1252_encoded_string, _, _) = encoding_rs::WINDOWS_1252.encode(&text);
+                file.write_all(&windows_1252_encoded_string)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -554,27 +1077,13 @@ impl DataSet {
         &self,
         cmd: &Commandline,
         fluid_data_set: &Fluid,
+        compliance: Option<&ComplianceRules>,
+        balls: Option<&BallRules>,
     ) -> Result<(), XlsxError> {
-        let mut excel = Excel::new(&cmd.xlsx());
+        let mut excel = Excel::new(&self.export_path(&cmd.xlsx()));
 
         let (special_events, _errors) = &self.get_special_events();
-        let fps = match cmd.fps() {
-            Some(25) => "25fps",
-            Some(50) => "50fps",
-            _ => "ms",
-        };
-
-        let header = vec![
-            "title".to_string(),
-            "filename".to_string(),
-            format!("start ({fps})"),
-            format!("end ({fps})"),
-            format!("duration ({fps})"),
-            format!("tcin ({fps})"),
-            format!("tcout ({fps})"),
-            "contentid".to_string(),
-            "logo".to_string(),
-        ];
+        let header = export_header(cmd);
         let mut data = vec![];
 
         special_events.iter().for_each(|special_event| {
@@ -587,7 +1096,32 @@ impl DataSet {
             data.push(vec![String::new()]);
         });
 
-        excel.write_file(&data)?;
+        excel.add_sheet(None, &data, &header)?;
+        if let Some(rules) = compliance {
+            let header = compliance_header();
+            let mut data = vec![header.clone()];
+            data.extend(self.compliance_rows(cmd, rules, fluid_data_set));
+            excel.add_sheet(Some(Msg::ComplianceSheet.text()), &data, &header)?;
+        }
+        if cmd.all() || cmd.advertising_time() {
+            let header = advertising_header();
+            let mut data = vec![header.clone()];
+            data.extend(self.advertising_rows(cmd).into_iter().map(|(_, row)| row));
+            excel.add_sheet(Some(Msg::AdvertisingSheet.text()), &data, &header)?;
+        }
+        if let Some(rules) = balls {
+            let header = ball_header();
+            let mut data = vec![header.clone()];
+            data.extend(self.ball_rows(cmd, rules));
+            excel.add_sheet(Some(Msg::BallSheet.text()), &data, &header)?;
+        }
+        if (cmd.all() || cmd.material_status()) && fluid_data_set.size() > 0 {
+            let header = material_header();
+            let mut data = vec![header.clone()];
+            data.extend(self.material_rows(cmd, fluid_data_set));
+            excel.add_sheet(Some(Msg::MaterialSheet.text()), &data, &header)?;
+        }
+        excel.save()?;
 
         Ok(())
     }
@@ -605,65 +1139,706 @@ impl DataSet {
         file.write_all(&windows_1252_encoded_string.as_ref())
     }
 
-    #[allow(dead_code)]
-    pub fn trailers_and_balls_mixup(&self, _summary: &mut Summary, cmd: &Commandline) {
-        let events: Vec<_> = self
+    // one row per ball with something unexpected before or after it
+    fn ball_rows(&self, cmd: &Commandline, rules: &BallRules) -> Vec<Vec<String>> {
+        trailer_balls::check(&self.eventcommands.define, rules)
+            .into_iter()
+            .map(|error| {
+                let (issue, expected) = match error.issue {
+                    BallIssue::Before => (Msg::BallBefore, &rules.before),
+                    BallIssue::After => (Msg::BallAfter, &rules.after),
+                };
+                vec![
+                    error.ball.get_title(),
+                    error.ball.starttime_to_string(cmd.utc(), cmd.fps()),
+                    issue.to_string(),
+                    error.neighbour.get_title(),
+                    error.neighbour.duration_to_string(cmd.fps()),
+                    error.kind.to_string(),
+                    expected
+                        .iter()
+                        .map(|kind| kind.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ]
+            })
+            .collect()
+    }
+
+    pub fn trailers_and_balls_mixup(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        rules: &BallRules,
+    ) {
+        let rows = self.ball_rows(cmd, rules);
+        summary.trailer_balls_error += rows.len() as i64;
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 20),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("issue", Msg::ColIssue.text(), 25),
+            Column::new("neighbour", Msg::ColNeighbour.text(), 30),
+            Column::new("duration", Msg::ColDuration.text(), 12),
+            Column::new("type", Msg::ColType.text(), 10),
+            Column::new("expected", Msg::ColExpected.text(), 20),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for row in rows {
+            let mut cells: Vec<ColoredString> = row.into_iter().map(|cell| cell.normal()).collect();
+            cells[2] = cells[2].clone().red();
+            cells[5] = cells[5].clone().red();
+            table.add_row(cells);
+        }
+        println!("{}", Msg::BallErrorsHeading.text().red());
+        table.print(cmd);
+    }
+
+    // pairs planned breaks with the special events, by start time when every
+    // planned break has one, otherwise in order
+    fn match_plan<'a, 'b>(
+        plan: &'b Plan,
+        special_events: &'b [SpecialEvent<'a>],
+        cmd: &Commandline,
+    ) -> Vec<(Option<&'b PlanBreak>, Option<&'b SpecialEvent<'a>>)> {
+        let special_events: Vec<&SpecialEvent> = special_events
+            .iter()
+            .filter(|x| !x.get_commercial_events().is_empty())
+            .collect();
+        let mut pairs = Vec::new();
+
+        if !plan.breaks.is_empty() && plan.breaks.iter().all(|b| b.start.is_some()) {
+            let mut used = vec![false; special_events.len()];
+            for planned in &plan.breaks {
+                let start = planned.start.as_ref().unwrap();
+                let nearest = special_events
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| !used[*i])
+                    .filter_map(|(i, x)| {
+                        let time = local_time(x.get_starttime()?, cmd.utc());
+                        Some((i, start.distance(time)))
+                    })
+                    .filter(|(_, distance)| *distance <= PLAN_START_TOLERANCE)
+                    .min_by_key(|(_, distance)| *distance);
+                match nearest {
+                    Some((i, _)) => {
+                        used[i] = true;
+                        pairs.push((Some(planned), Some(special_events[i])));
+                    }
+                    None => pairs.push((Some(planned), None)),
+                }
+            }
+            for (i, special_event) in special_events.iter().enumerate() {
+                if !used[i] {
+                    pairs.push((None, Some(*special_event)));
+                }
+            }
+        } else {
+            for i in 0..plan.breaks.len().max(special_events.len()) {
+                pairs.push((plan.breaks.get(i), special_events.get(i).copied()));
+            }
+        }
+        pairs
+    }
+
+    pub fn check_plan(&self, summary: &mut Summary, cmd: &Commandline, plan: &Plan) {
+        let (special_events, _) = self.get_special_events();
+        let tolerance = match cmd.fps() {
+            Some(fps) if fps > 0 => 1000 / fps,
+            _ => 40,
+        };
+        let duration = |d: i64| Event::a_duration_to_string(d, cmd.fps());
+
+        let mut table = Table::new(vec![
+            Column::new("block", Msg::ColBlock.text(), 12),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("issue", Msg::ColIssue.text(), 22),
+            Column::new("title", Msg::ColTitle.text(), 40),
+            Column::new("planned", Msg::ColPlanned.text(), 12),
+            Column::new("scheduled", Msg::ColScheduled.text(), 12),
+            Column::new("row", Msg::ColRow.text(), 6),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+
+        for (planned, special_event) in DataSet::match_plan(plan, &special_events, cmd) {
+            let spots = planned.map(|b| b.spots.clone()).unwrap_or_default();
+            let events = special_event
+                .map(|x| x.get_commercial_events())
+                .unwrap_or_default();
+            let scheduled: Vec<(String, i64)> = events
+                .iter()
+                .map(|e| (e.get_title(), e.get_duration()))
+                .collect();
+            let block = planned.map(|b| b.key.clone()).unwrap_or_default();
+            let start = match special_event.and_then(|x| x.get_starttime()) {
+                Some(time) => local_time(time, cmd.utc())
+                    .format("%d.%m.%Y %H:%M:%S")
+                    .to_string(),
+                None => planned
+                    .and_then(|b| b.start.as_ref())
+                    .map(|s| s.to_string())
+                    .unwrap_or_default(),
+            };
+
+            let title = |j: usize| {
+                scheduled[j]
+                    .0
+                    .replace(" - ", "")
+                    .replace(" UHD1_WERBUNG-01", "")
+            };
+            for deviation in plan::compare(&spots, &scheduled, tolerance) {
+                let (issue, title, planned, scheduled, row) = match deviation {
+                    Deviation::Missing(i) => {
+                        summary.plan_missing_error += 1;
+                        (Msg::PlanMissing, spots[i].title.clone(), duration(spots[i].duration), String::new(), spots[i].row.to_string())
+                    }
+                    Deviation::Extra(j) => {
+                        summary.plan_extra_error += 1;
+                        (Msg::PlanExtra, title(j), String::new(), duration(scheduled[j].1), String::new())
+                    }
+                    Deviation::Reordered(i, j) => {
+                        summary.plan_order_error += 1;
+                        (Msg::PlanReordered, spots[i].title.clone(), format!("#{}", i + 1), format!("#{}", j + 1), spots[i].row.to_string())
+                    }
+                    Deviation::Length(i, j) => {
+                        summary.plan_length_error += 1;
+                        (Msg::PlanLength, spots[i].title.clone(), duration(spots[i].duration), duration(scheduled[j].1), spots[i].row.to_string())
+                    }
+                };
+                table.add_row(vec![
+                    block.as_str().normal(),
+                    start.as_str().normal(),
+                    issue.text().red(),
+                    title.normal(),
+                    planned.normal(),
+                    scheduled.normal(),
+                    row.normal(),
+                ]);
+            }
+            table.add_separator();
+        }
+
+        if !table.is_empty() {
+            println!("{}", Msg::PlanDeviations.text().red());
+            table.print(cmd);
+        }
+    }
+
+    pub fn airings(&self, rules: &RerunRules, fluid: &Fluid) -> Vec<Airing> {
+        let (special_events, _) = self.get_special_events();
+        reruns::airings(&self.eventcommands.define, &special_events, rules, fluid)
+    }
+
+    pub fn check_day_coverage(&self, summary: &mut Summary, cmd: &Commandline) {
+        let days = day_coverage::check(&self.eventcommands.define, cmd.day_start(), cmd.utc());
+        if days.is_empty() {
+            return;
+        }
+
+        let duration = |d: i64| Event::a_duration_to_string(d, cmd.fps());
+        let hole = |hole: Option<(DateTime<Utc>, DateTime<Utc>)>| match hole {
+            Some((start, end)) => duration((end - start).num_milliseconds()).red(),
+            None => String::new().normal(),
+        };
+        let mut table = Table::new(vec![
+            Column::new("day", Msg::ColDay.text(), 11),
+            Column::new("type", Msg::ColType.text(), 8),
+            Column::new("expected", Msg::ColExpected.text(), 12),
+            Column::new("scheduled", Msg::ColScheduled.text(), 12),
+            Column::new("missing", Msg::ColMissing.text(), 12),
+            Column::new("leading", Msg::ColLeading.text(), 15),
+            Column::new("trailing", Msg::ColTrailing.text(), 15),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for day in &days {
+            summary.day_coverage_error +=
+                day.leading.is_some() as i64 + day.trailing.is_some() as i64;
+            let missing = duration(day.expected - day.scheduled);
+            table.add_row(vec![
+                day.day.format("%d.%m.%Y").to_string().normal(),
+                day.kind.normal(),
+                duration(day.expected).normal(),
+                duration(day.scheduled).normal(),
+                if day.scheduled < day.expected {
+                    missing.red()
+                } else {
+                    missing.normal()
+                },
+                hole(day.leading),
+                hole(day.trailing),
+            ]);
+        }
+        println!("{}", Msg::DayCoverage);
+        table.print(cmd);
+    }
+
+    pub fn check_sendepause(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        rules: &SendepauseRules,
+    ) {
+        let errors = sendepause::check(&self.eventcommands.define, rules);
+        summary.sendepause_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let time = |t| {
+            local_time(t, cmd.utc())
+                .format("%d.%m.%Y %H:%M:%S%.3f")
+                .to_string()
+        };
+        let mut table = Table::new(vec![
+            Column::new("programme", Msg::ColProgramme.text(), 20),
+            Column::new("type", Msg::ColType.text(), 11),
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("contentid", Msg::ColContentId.text(), 20),
+            Column::new("issue", Msg::ColIssue.text(), 30),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("end", Msg::ColEnd.text(), 23),
+            Column::new("duration", Msg::ColDuration.text(), 12),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let (kind, title, contentid) = match error.event {
+                Some((kind, event)) => (kind, event.get_title(), event.get_contentid()),
+                None => ("", String::new(), String::new()),
+            };
+            table.add_row(vec![
+                error
+                    .pause
+                    .map(|p| p.get_title())
+                    .unwrap_or_default()
+                    .normal(),
+                kind.normal(),
+                title.normal(),
+                contentid.normal(),
+                error.issue.msg().text().red(),
+                time(error.start).normal(),
+                time(error.end).normal(),
+                Event::a_duration_to_string(error.duration(), cmd.fps()).normal(),
+            ]);
+        }
+        println!("{}", Msg::Sendepausen.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_identifiers(&self, summary: &mut Summary, cmd: &Commandline) {
+        let errors = identifiers::check(&self.eventcommands.define, cmd.programid_pattern());
+        summary.identifier_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("type", Msg::ColType.text(), 11),
+            Column::new("eventid", Msg::ColEventId.text(), 12),
+            Column::new("serviceid", Msg::ColServiceId.text(), 10),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("contentid", Msg::ColContentId.text(), 20),
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("issue", Msg::ColIssue.text(), 30),
+            Column::new("detail", Msg::ColDetail.text(), 30),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let event = error.define.get_event();
+            table.add_row(vec![
+                error.define.kind().normal(),
+                event.get_eventid().normal(),
+                event.get_serviceid().normal(),
+                event.get_programid().normal(),
+                event.get_contentid().normal(),
+                event.get_title().normal(),
+                error.issue.msg().text().red(),
+                error.detail.clone().normal(),
+            ]);
+        }
+        println!("{}", Msg::Identifiers.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_logo_coverage(&self, summary: &mut Summary, cmd: &Commandline) {
+        let (special_events, _) = self.get_special_events();
+        let errors = logo_coverage::check(&self.eventcommands.define, &special_events, cmd);
+        summary.logo_coverage_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let time = |t| {
+            local_time(t, cmd.utc())
+                .format("%d.%m.%Y %H:%M:%S%.3f")
+                .to_string()
+        };
+        let mut table = Table::new(vec![
+            Column::new("programme", Msg::ColProgramme.text(), 30),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("issue", Msg::ColIssue.text(), 28),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("end", Msg::ColEnd.text(), 23),
+            Column::new("duration", Msg::ColDuration.text(), 12),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let issue = match error.issue {
+                LogoIssue::Missing => Msg::LogoMissing,
+                LogoIssue::LateStart => Msg::LogoLateStart,
+                LogoIssue::EarlyEnd => Msg::LogoEarlyEnd,
+                LogoIssue::Gap => Msg::LogoGap,
+                LogoIssue::IntoBreak => Msg::LogoIntoBreak,
+            };
+            table.add_row(vec![
+                error
+                    .programme
+                    .map(|p| p.get_title())
+                    .unwrap_or_default()
+                    .normal(),
+                error.event.get_programid().normal(),
+                error.event.get_title().normal(),
+                issue.text().red(),
+                time(error.start).normal(),
+                time(error.end).normal(),
+                Event::a_duration_to_string(error.duration(), cmd.fps()).normal(),
+            ]);
+        }
+        println!("{}", Msg::LogoCoverage.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_layout_conflicts(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        rules: &LayoutRules,
+    ) {
+        let mut events: Vec<_> = self
             .eventcommands
             .define
             .iter()
-            .filter(|x| {
-                if let Define::siEvent(..) = x {
-                    false
-                } else {
-                    true
+            .filter_map(|define| match define {
+                Define::logoEvent(event) | Define::layoutEvent(event) => {
+                    Some((event, event.get_starttime()?, event.get_endtime()?))
                 }
+                _ => None,
             })
-            .collect::<Vec<&Define>>();
+            .collect();
+        events.sort_by_key(|(_, start, _)| *start);
 
-        events.iter().enumerate().for_each(|(i, x)| {
-            let event = x.get_event();
-            if event.get_contentid().contains("5675d8c63df2424bf286") {
-                let before = events[i - 1].get_event();
-                let after = events[i + 1].get_event();
+        let time = |t| {
+            local_time(t, cmd.utc())
+                .format("%d.%m.%Y %H:%M:%S%.3f")
+                .to_string()
+        };
+        let name = |event: &Event| format!("{} ({})", event.get_logo(), event.get_contentid());
+        let mut table = Table::new(vec![
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("end", Msg::ColEnd.text(), 23),
+            Column::new("first", Msg::ColFirst.text(), 40),
+            Column::new("second", Msg::ColSecond.text(), 40),
+            Column::new("area", Msg::ColArea.text(), 16),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        let mut conflicts = Vec::new();
+        for (i, (first, start, end)) in events.iter().enumerate() {
+            for (second, second_start, second_end) in &events[i + 1..] {
+                if second_start >= end {
+                    break;
+                }
+                if let Some(conflict) =
+                    rules.conflict(&first.get_contentid(), &second.get_contentid())
+                {
+                    conflicts.push((
+                        *second_start.max(start),
+                        *end.min(second_end),
+                        first,
+                        second,
+                        conflict,
+                    ));
+                }
+            }
+        }
+        conflicts.sort_by_key(|(start, ..)| *start);
+        summary.layout_conflict_error += conflicts.len() as i64;
+
+        for (start, end, first, second, conflict) in conflicts {
+            let area = match conflict {
+                Conflict::Area(area) => area,
+                Conflict::Rule => Msg::LayoutRule.to_string(),
+            };
+            table.add_row(vec![
+                time(start).normal(),
+                time(end).normal(),
+                name(first).normal(),
+                name(second).normal(),
+                area.red(),
+            ]);
+        }
 
-                if 10 * 1_000 < before.get_duration() && before.get_duration() < 30 * 1_000 {
-                    //before is trailer
-                } else if 3 * 60_000 < before.get_duration() {
-                    // before is segment
-                } else {
+        if !table.is_empty() {
+            println!("{}", Msg::LayoutConflicts.text().red());
+            table.print(cmd);
+        }
+    }
+
+    fn compliance_rows(
+        &self,
+        cmd: &Commandline,
+        rules: &ComplianceRules,
+        fluid: &Fluid,
+    ) -> Vec<Vec<String>> {
+        let time = |t| {
+            local_time(t, cmd.utc())
+                .format("%d.%m.%Y %H:%M:%S%.3f")
+                .to_string()
+        };
+        compliance::check(&self.eventcommands.define, rules, fluid)
+            .iter()
+            .map(|error| {
+                vec![
+                    format!("{} ({})", error.rule, error.layout),
+                    error.event.get_title(),
+                    error.event.get_programid(),
+                    error.issue.msg().to_string(),
+                    time(error.start),
+                    time(error.end),
+                    Event::a_duration_to_string(error.duration(), cmd.fps()),
+                ]
+            })
+            .collect()
+    }
+
+    pub fn check_compliance(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        rules: &ComplianceRules,
+        fluid: &Fluid,
+    ) {
+        let rows = self.compliance_rows(cmd, rules, fluid);
+        summary.compliance_error += rows.len() as i64;
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("rule", Msg::ColRule.text(), 36),
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("issue", Msg::ColIssue.text(), 28),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("end", Msg::ColEnd.text(), 23),
+            Column::new("uncovered", Msg::ColUncovered.text(), 12),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for row in rows {
+            let mut cells: Vec<ColoredString> = row.into_iter().map(|cell| cell.normal()).collect();
+            cells[3] = cells[3].clone().red();
+            table.add_row(cells);
+        }
+        println!("{}", Msg::ComplianceErrorsHeading.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_interruptions(&self, summary: &mut Summary, cmd: &Commandline) {
+        let (special_events, _) = self.get_special_events();
+        let min_segment = cmd.min_segment() * 60_000;
+        let break_interval = cmd.break_interval() * 60_000;
+        let errors = interruptions::check(
+            &self.eventcommands.define,
+            &special_events,
+            min_segment,
+            break_interval,
+        );
+        summary.interruption_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let time = |t| {
+            local_time(t, cmd.utc())
+                .format("%d.%m.%Y %H:%M:%S%.3f")
+                .to_string()
+        };
+        let duration = |d: i64| Event::a_duration_to_string(d, cmd.fps());
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("programid", Msg::ColProgramId.text(), 15),
+            Column::new("issue", Msg::ColIssue.text(), 28),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("end", Msg::ColEnd.text(), 23),
+            Column::new("length", Msg::ColLength.text(), 12),
+            Column::new("minimum", Msg::ColMinimum.text(), 12),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let (issue, minimum) = match error.issue {
+                InterruptionIssue::ShortSegment => (Msg::InterruptionShortSegment, min_segment),
+                InterruptionIssue::BreaksTooClose => {
+                    (Msg::InterruptionBreaksTooClose, break_interval)
+                }
+            };
+            table.add_row(vec![
+                error.event.get_title().normal(),
+                error.event.get_programid().normal(),
+                issue.text().red(),
+                time(error.start).normal(),
+                time(error.end).normal(),
+                duration(error.length).red(),
+                duration(minimum).normal(),
+            ]);
+        }
+        println!("{}", Msg::Interruptions.text().red());
+        table.print(cmd);
+    }
+
+    pub fn check_youth_protection(
+        &self,
+        summary: &mut Summary,
+        cmd: &Commandline,
+        rules: &YouthRules,
+        fluid: &Fluid,
+    ) {
+        let errors = youth_protection::check(&self.eventcommands.define, rules, fluid);
+        summary.youth_protection_error += errors.len() as i64;
+        if errors.is_empty() {
+            return;
+        }
+
+        let time = |t: NaiveDateTime| t.format("%d.%m.%Y %H:%M:%S%.3f").to_string();
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("contentid", Msg::ColContentId.text(), 20),
+            Column::new("rating", Msg::ColRating.text(), 6),
+            Column::new("window", Msg::ColWindow.text(), 11),
+            Column::new("issue", Msg::ColIssue.text(), 20),
+            Column::new("start", Msg::ColStart.text(), 23),
+            Column::new("end", Msg::ColEnd.text(), 23),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for error in &errors {
+            let window = format!(
+                "{}-{}",
+                error.window.from.format("%H:%M"),
+                error.window.until.format("%H:%M")
+            );
+            table.add_row(vec![
+                error.event.get_title().normal(),
+                error.event.get_contentid().normal(),
+                error.age.to_string().normal(),
+                window.normal(),
+                error.issue.msg().text().red(),
+                time(error.start).normal(),
+                time(error.end).normal(),
+            ]);
+        }
+        println!("{}", Msg::YouthProtection.text().red());
+        table.print(cmd);
+    }
+
+    // the commercials of the breaks and everything matching --ad-content-ids
+    fn advertising_events(&self, cmd: &Commandline) -> Vec<&Event> {
+        let (special_events, _) = self.get_special_events();
+        let mut events: Vec<&Event> = special_events
+            .iter()
+            .flat_map(|special_event| special_event.get_commercial_events())
+            .collect();
+        let ids = cmd.ad_content_ids();
+        for define in &self.eventcommands.define {
+            if let Define::vaEvent(event) = define {
+                let id = event.get_contentid();
+                if ids.iter().any(|x| id.contains(x.as_str()))
+                    && !events.iter().any(|e| std::ptr::eq(*e, event))
+                {
+                    events.push(event);
                 }
-                println!("{}", before.get_duration());
-                //                assert!(before.get_duration() < 30 * 1_000);
-
-                print_line(100);
-                println!(
-                    "| {} | {} | {} |",
-                    before.get_title().take(50),
-                    before.starttime_to_string(cmd.utc(), cmd.fps()),
-                    before.duration_to_string(cmd.fps()).take(20),
-                );
-
-                print_line(100);
-                println!(
-                    "| {} | {} | {} |",
-                    event.get_title().take(50).blue(),
-                    event.starttime_to_string(cmd.utc(), cmd.fps()).blue(),
-                    event.duration_to_string(cmd.fps()).take(20).blue(),
-                );
-
-                print_line(100);
-
-                println!(
-                    "| {} | {} | {} |",
-                    after.get_title().take(50),
-                    after.starttime_to_string(cmd.utc(), cmd.fps()),
-                    after.duration_to_string(cmd.fps()).take(20),
-                );
-
-                print_line(100);
-                println!("");
             }
-        });
+        }
+        events
+    }
+
+    // one row per window with advertising, true if it is over the limit
+    fn advertising_rows(&self, cmd: &Commandline) -> Vec<(bool, Vec<String>)> {
+        let spots: Vec<_> = self
+            .advertising_events(cmd)
+            .iter()
+            .filter_map(|event| Some((event.get_starttime()?, event.get_endtime()?)))
+            .collect();
+        let limit = cmd.ad_limit() * 1000;
+        let duration = |d: i64| Event::a_duration_to_string(d, cmd.fps());
+        let format = |time| local_time(time, cmd.utc()).format("%d.%m.%Y %H:%M").to_string();
+
+        advertising::per_window(&spots, cmd.ad_window(), cmd.utc())
+            .into_iter()
+            .map(|(start, end, total)| {
+                let share = total as f64 * 100.0 / (end - start).num_milliseconds() as f64;
+                (
+                    total > limit,
+                    vec![
+                        format(start),
+                        format(end),
+                        duration(total),
+                        duration(limit),
+                        format!("{:.1}%", share),
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    pub fn check_advertising_time(&self, summary: &mut Summary, cmd: &Commandline) {
+        let rows = self.advertising_rows(cmd);
+        if rows.is_empty() {
+            return;
+        }
+
+        let mut table = Table::new(vec![
+            Column::new("start", Msg::ColStart.text(), 16),
+            Column::new("end", Msg::ColEnd.text(), 16),
+            Column::new("advertising", Msg::ColAdvertising.text(), 12),
+            Column::new("limit", Msg::ColLimit.text(), 12),
+            Column::new("share", Msg::ColShare.text(), 8),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for (over, row) in rows {
+            let mut cells: Vec<ColoredString> = row.into_iter().map(|cell| cell.normal()).collect();
+            if over {
+                summary.advertising_error += 1;
+                cells[2] = cells[2].clone().red();
+                cells[4] = cells[4].clone().red();
+            }
+            table.add_row(cells);
+        }
+        println!("{}", Msg::AdvertisingPerWindow.fill(&[&cmd.ad_window()]));
+        table.print(cmd);
     }
 
     pub fn print_va_errors(&self, summary: &mut Summary, cmd: &Commandline) {
@@ -682,12 +1857,24 @@ impl DataSet {
             .len() as i64;
 
         if (summary.va_errors != 0 || summary.id_errors != 0) && cmd.verbose() {
-            println!("VaEvent errors and id errors:");
-            table_print::print_header_short();
+            println!("{}", Msg::VaAndIdErrors);
+            let mut table = Table::new(vec![
+                Column::new("title", Msg::ColTitle.text(), 30),
+                Column::new("programid", Msg::ColProgramId.text(), 15),
+                Column::new("start", Msg::ColStart.text(), 23),
+                Column::new("end", Msg::ColEnd.text(), 23),
+                Column::new("duration", Msg::ColDuration.text(), 12),
+                Column::new("contentid", Msg::ColContentId.text(), 20),
+            ]);
+            if let Some(columns) = cmd.columns() {
+                table.select(&columns);
+            }
             for (time_error, event) in va_events {
-                event.print_va_event_verbose(time_error, cmd.utc(), cmd.fps());
+                if let Some(row) = event.va_event_row(time_error, cmd.utc(), cmd.fps()) {
+                    table.add_row(row);
+                }
             }
-            table_print::print_header_short();
+            table.print(cmd);
         }
     }
 
@@ -852,26 +2039,173 @@ impl DataSet {
             summary.text_error += 1;
             if cmd.verbose() {
                 let event = s.get_event();
-                store.push(format!(
-                    "| {} | {} | {} | {} |",
-                    event.get_title().take(50),
-                    event.get_programid().take(15),
-                    event.starttime_to_string(cmd.utc(), cmd.fps()).take(23),
-                    event.endtime_to_string(cmd.utc(), cmd.fps()).take(23)
-                ));
+                store.push(vec![
+                    event.get_title().normal(),
+                    event.get_programid().normal(),
+                    event.starttime_to_string(cmd.utc(), cmd.fps()).normal(),
+                    event.endtime_to_string(cmd.utc(), cmd.fps()).normal(),
+                ]);
             }
         }
         if store.len() > 0 && cmd.verbose() {
-            let len = 122;
-            println!("{}", "Missings texts:".red());
-            table_print::missing_text_header();
-            store.iter().enumerate().for_each(|(i, x)| {
-                println!("{}", x);
-                if i < store.len() - 1 {
-                    println!("|{}|", "-".repeat(len as usize));
-                }
-            });
-            table_print::missing_text_header();
+            println!("{}", Msg::MissingTextsHeading.text().red());
+            let mut table = Table::new(vec![
+                Column::new("title", Msg::ColTitle.text(), 50),
+                Column::new("programid", Msg::ColProgramId.text(), 15),
+                Column::new("start", Msg::ColStart.text(), 23),
+                Column::new("end", Msg::ColEnd.text(), 23),
+            ]);
+            if let Some(columns) = cmd.columns() {
+                table.select(&columns);
+            }
+            for row in store {
+                table.add_row(row);
+                table.add_separator();
+            }
+            table.print(cmd);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{at, berlin, minutes, Pts};
+
+    #[test]
+    fn layouts_sharing_an_area_while_overlapping() {
+        let mut pts = Pts::new();
+        pts.va("Film", "1572515-971182", "18:00:00", minutes(60), "P100")
+            .logo("HDPLUHD_LOGO_2", "18:00:00", minutes(60), "P100")
+            .layout("HDPLUHD_LAY_1", "18:10:00", minutes(1), "P100")
+            .layout("HDPLUHD_LAY_7", "18:20:00", minutes(1), "P100")
+            .layout("HDPLUHD_LAY_11", "18:30:00", minutes(5), "P100")
+            .layout("HDPLUHD_LAY_10", "18:35:00", minutes(5), "P100");
+        let cmd = Commandline::from_args(&[]);
+        let mut summary = Summary::new();
+        pts.dataset()
+            .check_layout_conflicts(&mut summary, &cmd, &LayoutRules::default_rules());
+        // only LAY_1 shares the top right corner with the logo, LAY_10 starts
+        // when LAY_11 ends
+        assert_eq!(summary.layout_conflict_error, 1);
+    }
+
+    #[test]
+    fn advertising_in_breaks_and_listed_content_ids() {
+        let mut pts = Pts::new();
+        pts.va("Dranbleiben", "3ecea6b8084f35c2634d", "18:50:00", 10_000, "D1")
+            .va("Spot UHD1_WERBUNG-01", "UHD1_WERBUNG-01", "18:50:10", minutes(8), "W1")
+            .va("Spot UHD1_WERBUNG-01", "UHD1_WERBUNG-01", "18:58:10", minutes(4), "W2")
+            .va("Bald weiter", "02e9d82071c7735de5cd", "19:02:10", 10_000, "E1")
+            .va("Teleshopping", "SHOP-1", "19:30:00", minutes(2), "S1");
+        let cmd = Commandline::from_args(&["--utc", "--ad-content-ids", "SHOP-"]);
+        let rows = pts.dataset().advertising_rows(&cmd);
+        let over: Vec<(bool, &str, &str)> = rows
+            .iter()
+            .map(|(over, row)| (*over, row[0].as_str(), row[4].as_str()))
+            .collect();
+        assert_eq!(
+            over,
+            [
+                (false, "01.05.2024 18:00", "16.4%"),
+                (false, "01.05.2024 19:00", "6.9%"),
+            ]
+        );
+
+        let cmd = Commandline::from_args(&["--utc", "--ad-limit", "300"]);
+        let rows = pts.dataset().advertising_rows(&cmd);
+        assert_eq!(rows.iter().filter(|(over, _)| *over).count(), 1);
+    }
+
+    #[test]
+    fn tc_out_past_the_end_of_the_material() {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(20))
+            .offset(minutes(50))
+            .va(" - 001 Serie", "1600000-100001", "18:20:00", minutes(20), "P200-1")
+            .offset(minutes(10));
+        let fluid = Fluid::from_entries(&[
+            &[("ContentId", "1572515-971182"), ("RuntimeMs", "01:00:00.0000")],
+            &[("ContentId", "1600000-100001"), ("RuntimeMs", "00:30:00.0000")],
+        ]);
+        let cmd = Commandline::from_args(&[]);
+        let mut summary = Summary::new();
+        pts.dataset()
+            .list_vaevents_with_length_errors(&mut summary, &cmd, &fluid);
+        assert_eq!(summary.content_to_long_error, 1);
+    }
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn hours((start, end): (DateTime<Utc>, DateTime<Utc>)) -> i64 {
+        (end - start).num_hours()
+    }
+
+    #[test]
+    fn broadcast_days_have_23_or_25_hours_across_dst() {
+        berlin();
+        let bounds = day_bounds(date("2024-03-30"), 6, false);
+        assert_eq!(bounds, (at("2024-03-30T05:00:00"), at("2024-03-31T04:00:00")));
+        assert_eq!(hours(bounds), 23);
+        assert_eq!(hours(day_bounds(date("2024-03-31"), 6, false)), 24);
+        let bounds = day_bounds(date("2024-10-26"), 6, false);
+        assert_eq!(bounds, (at("2024-10-26T04:00:00"), at("2024-10-27T05:00:00")));
+        assert_eq!(hours(bounds), 25);
+        // a day starting at midnight changes on the day itself
+        assert_eq!(hours(day_bounds(date("2024-03-31"), 0, false)), 23);
+        assert_eq!(hours(day_bounds(date("2024-10-27"), 0, false)), 25);
+        assert_eq!(hours(day_bounds(date("2024-10-26"), 6, true)), 24);
+    }
+
+    #[test]
+    fn broadcast_day_of_a_time_across_dst() {
+        berlin();
+        let day = |time| broadcast_day(at(time), 6, false).to_string();
+        // 05:59 and 06:00 in winter and in summer time
+        assert_eq!(day("2024-03-31T03:59:00"), "2024-03-30");
+        assert_eq!(day("2024-03-31T04:00:00"), "2024-03-31");
+        assert_eq!(day("2024-10-27T04:59:00"), "2024-10-26");
+        assert_eq!(day("2024-10-27T05:00:00"), "2024-10-27");
+        assert_eq!(broadcast_day(at("2024-10-27T05:00:00"), 6, true).to_string(), "2024-10-26");
+    }
+
+    #[test]
+    fn utc_time_of_local_times_across_dst() {
+        berlin();
+        let time = |text| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S").unwrap();
+        assert_eq!(utc_time(time("2024-05-01T20:00:00"), false), at("2024-05-01T18:00:00"));
+        assert_eq!(utc_time(time("2024-05-01T20:00:00"), true), at("2024-05-01T20:00:00"));
+        // 02:30 happens twice in october, the earlier one is meant
+        assert_eq!(utc_time(time("2024-10-27T02:30:00"), false), at("2024-10-27T00:30:00"));
+        assert_eq!(utc_time(time("2024-10-27T03:00:00"), false), at("2024-10-27T02:00:00"));
+        assert_eq!(utc_time(time("2024-10-27T03:30:00"), false), at("2024-10-27T02:30:00"));
+        // 02:30 is skipped in march, the clocks go forward at 01:00 utc
+        assert_eq!(utc_time(time("2024-03-31T02:30:00"), false), at("2024-03-31T01:00:00"));
+    }
+
+    #[test]
+    fn select_day_keeps_blocks_together() {
+        berlin();
+        let mut pts = Pts::new();
+        pts.va("Nacht", "1000000-000001", "2024-10-27T03:00:00", minutes(100), "P1")
+            .va("Dranbleiben", "3ecea6b8084f35c2634d", "2024-10-27T04:40:00", minutes(10), "D1")
+            .va("Spot", "UHD1_WERBUNG-01", "2024-10-27T04:50:00", minutes(20), "W1")
+            .va("Bald weiter", "02e9d82071c7735de5cd", "2024-10-27T05:10:00", 10_000, "E1")
+            .va("Morgen", "1000000-000002", "2024-10-27T05:10:10", minutes(60), "P2");
+        let titles = |day: &str| {
+            let mut dataset = pts.dataset();
+            dataset.select_day(date(day), 6, false);
+            dataset
+                .defines()
+                .iter()
+                .map(|define| define.get_event().get_title())
+                .collect::<Vec<_>>()
+        };
+        // the broadcast day starts at 06:00 local winter time (05:00 utc), the
+        // Bald weiter at 06:10 stays on the 26th with its block from 05:40
+        assert_eq!(titles("2024-10-26"), ["Nacht", "Dranbleiben", "Spot", "Bald weiter"]);
+        assert_eq!(titles("2024-10-27"), ["Morgen"]);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::pts_loader::dataset::{broadcast_day, day_bounds};
+use crate::pts_loader::define::Define;
+use crate::pts_loader::span::{merge, span, uncovered, Span};
+use chrono::NaiveDate;
+
+pub struct DayCoverage {
+    pub kind: &'static str,
+    pub day: NaiveDate,
+    pub expected: i64,
+    pub scheduled: i64,
+    // nothing scheduled from the start of the day until ...
+    pub leading: Option<Span>,
+    // ... and from ... until the end of the day
+    pub trailing: Option<Span>,
+}
+
+fn length((start, end): Span) -> i64 {
+    (end - start).num_milliseconds()
+}
+
+// Every broadcast day an event starts in has to be covered from its first to
+// its last millisecond, by the vaEvents as well as by the siEvents.
+pub fn check(defines: &[Define], day_start: u32, utc: bool) -> Vec<DayCoverage> {
+    let mut result = Vec::new();
+    for kind in ["vaEvent", "siEvent"] {
+        let spans: Vec<Span> = defines
+            .iter()
+            .filter(|define| define.kind() == kind)
+            .filter_map(|define| span(define.get_event()))
+            .collect();
+        let mut days: Vec<NaiveDate> = spans
+            .iter()
+            .map(|(start, _)| broadcast_day(*start, day_start, utc))
+            .collect();
+        days.sort();
+        days.dedup();
+        let spans = merge(spans);
+
+        for day in days {
+            let bounds = day_bounds(day, day_start, utc);
+            let holes = uncovered(bounds, &spans);
+            let missing: i64 = holes.iter().map(|hole| length(*hole)).sum();
+            result.push(DayCoverage {
+                kind,
+                day,
+                expected: length(bounds),
+                scheduled: length(bounds) - missing,
+                leading: holes.first().filter(|hole| hole.0 == bounds.0).copied(),
+                trailing: holes.last().filter(|hole| hole.1 == bounds.1).copied(),
+            });
+        }
+    }
+    result.sort_by_key(|coverage| coverage.day);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{at, minutes, Pts};
+
+    fn hours(n: i64) -> i64 {
+        minutes(n * 60)
+    }
+
+    // kind, day, missing minutes, leading and trailing hole
+    type Row = (&'static str, String, i64, Option<Span>, Option<Span>);
+
+    fn coverage(pts: &Pts) -> Vec<Row> {
+        check(&pts.defines(), 6, true)
+            .iter()
+            .map(|c| {
+                let missing = (c.expected - c.scheduled) / 60_000;
+                (c.kind, c.day.to_string(), missing, c.leading, c.trailing)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn covered_broadcast_day() {
+        let mut pts = Pts::new();
+        pts.va("Morgen", "1000000-000001", "06:00:00", hours(12), "P1")
+            .va("Abend", "1000000-000002", "18:00:00", hours(12), "P2")
+            .si("Tag", "06:00:00", hours(12), "P1")
+            .si("Nacht", "18:00:00", hours(12), "P2");
+        assert_eq!(
+            coverage(&pts),
+            [
+                ("vaEvent", "2024-05-01".to_string(), 0, None, None),
+                ("siEvent", "2024-05-01".to_string(), 0, None, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn holes_at_the_start_in_between_and_at_the_end() {
+        let mut pts = Pts::new();
+        pts.va(
+            "Morgen",
+            "1000000-000001",
+            "06:30:00",
+            hours(11) + minutes(30),
+            "P1",
+        )
+        .va("Abend", "1000000-000002", "18:10:00", hours(10), "P2")
+        .va(
+            "Nacht",
+            "1000000-000003",
+            "2024-05-02T05:59:00",
+            hours(1),
+            "P3",
+        );
+        let (start, end) = (at("2024-05-01T06:00:00"), at("2024-05-02T06:00:00"));
+        assert_eq!(
+            coverage(&pts),
+            [(
+                "vaEvent",
+                "2024-05-01".to_string(),
+                30 + 10 + 109,
+                Some((start, at("06:30:00"))),
+                None,
+            )]
+        );
+
+        let mut pts = Pts::new();
+        pts.si("Tag", "06:00:00", hours(12), "P1")
+            .si("Nacht", "18:00:00", hours(11), "P2");
+        assert_eq!(
+            coverage(&pts),
+            [(
+                "siEvent",
+                "2024-05-01".to_string(),
+                60,
+                None,
+                Some((at("2024-05-02T05:00:00"), end)),
+            )]
+        );
+    }
+}
@@ -1,10 +1,9 @@
 use super::event::*;
-use crate::utils::take::Take;
-use crate::{
-    commandline::commandline::Commandline, commandline::commandline::Range, utils::table_print,
-};
+use crate::utils::messages::Msg;
+use crate::utils::table::{Column, Table};
+use crate::commandline::commandline::Commandline;
 use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone, Utc};
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -109,13 +108,12 @@ impl SiError {
 }
 
 impl Define {
-    fn is_in_range(&self, range: Option<Range>) -> bool {
-        match range {
-            None => return false,
-            Some(range) => match self.get_event().get_starttime() {
-                None => return false,
-                Some(time) => return range.start_time <= time && time <= range.end_time,
-            },
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Define::vaEvent(..) => "vaEvent",
+            Define::logoEvent(..) => "logoEvent",
+            Define::layoutEvent(..) => "layoutEvent",
+            Define::siEvent(..) => "siEvent",
         }
     }
 
@@ -148,12 +146,15 @@ impl Define {
         }
     }
 
-    pub fn print_va_event_verbose(&self, time_error: &bool, utc: bool, fps: Option<i64>) {
+    pub fn va_event_row(
+        &self,
+        time_error: &bool,
+        utc: bool,
+        fps: Option<i64>,
+    ) -> Option<Vec<ColoredString>> {
         match self {
-            Define::vaEvent(event) => {
-                event.print_vaevent_verbose(time_error, utc, fps);
-            }
-            _ => (),
+            Define::vaEvent(event) => Some(event.vaevent_row(time_error, utc, fps)),
+            _ => None,
         }
     }
 
@@ -186,46 +187,25 @@ impl SiEvents<'_> {
         self.events.len()
     }
 
-    fn print_head(&self) {
-        println!(
-            "| {} | {} | {} | {} |",
-            "Title".to_string().take(30).blue(),
-            "StartTime".to_string().take(25).blue(),
-            "EndTime".to_string().take(25).blue(),
-            "ProgramId".to_string().take(20).blue(),
-        );
-    }
-
-    fn filter_range(&mut self, cmd: &Commandline) {
-        self.events = self
-            .events
-            .clone()
-            .into_iter()
-            .filter(|x| {
-                x.is_in_range(cmd.valid_range())
-            })
-            .collect();
-    }
-
     pub fn print(&mut self, cmd: &Commandline) {
-        let mut footer = false;
-        self.filter_range(cmd);
-        table_print::print_line(111);
-        self.print_head();
-        self.events.iter().enumerate().for_each(|(i, x)| {
+        let mut table = Table::new(vec![
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("start", Msg::ColStart.text(), 25),
+            Column::new("end", Msg::ColEnd.text(), 25),
+            Column::new("programid", Msg::ColProgramId.text(), 20),
+        ]);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        if !cmd.display_sievents_only_sendepausen() {
+            table.repeat_header(5);
+        }
+        self.events.iter().for_each(|x| {
             let event = x.get_event();
-            let mut title = event.get_title().take(30).red().clear();
-            let mut starttime = event
-                .starttime_to_string(cmd.utc(), cmd.fps())
-                .take(25)
-                .red()
-                .clear();
-            let mut endtime = event
-                .endtime_to_string(cmd.utc(), cmd.fps())
-                .take(25)
-                .red()
-                .clear();
-            let mut programid = event.get_programid().take(20).red().clear();
+            let mut title = event.get_title().red().clear();
+            let mut starttime = event.starttime_to_string(cmd.utc(), cmd.fps()).red().clear();
+            let mut endtime = event.endtime_to_string(cmd.utc(), cmd.fps()).red().clear();
+            let mut programid = event.get_programid().red().clear();
             if title.contains("Sendepause") {
                 title = title.red();
                 starttime = starttime.red();
@@ -233,32 +213,11 @@ impl SiEvents<'_> {
                 programid = programid.red();
             }
 
-            if cmd.display_sievents_only_sendepausen() {
-                if title.contains("Sendepause") {
-                    println!(
-                        "| {} | {} | {} | {} |",
-                        title, starttime, endtime, programid,
-                    );
-                    table_print::print_line(111);
-                }
-            } else {
-                println!(
-                    "| {} | {} | {} | {} |",
-                    title, starttime, endtime, programid,
-                );
-                table_print::print_line(111);
-                if i % 5 == 0 {
-                    self.print_head();
-                    table_print::print_line(111);
-                    footer = true;
-                } else {
-                    footer = false;
-                }
+            if !cmd.display_sievents_only_sendepausen() || title.contains("Sendepause") {
+                table.add_row(vec![title, starttime, endtime, programid]);
+                table.add_separator();
             }
         });
-        if !footer {
-            self.print_head();
-            table_print::print_line(111);
-        }
+        table.print(cmd);
     }
 }
@@ -0,0 +1,157 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::sistandard::TEXT_FIELDS;
+use crate::utils::epg_rules::EpgRules;
+use crate::utils::messages::Msg;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextIssue {
+    Missing,
+    TooLong,
+    // characters missing from the DVB character table
+    NotEncodable,
+    Language,
+    Placeholder,
+}
+
+impl TextIssue {
+    pub fn msg(self) -> Msg {
+        match self {
+            TextIssue::Missing => Msg::EpgMissing,
+            TextIssue::TooLong => Msg::EpgTooLong,
+            TextIssue::NotEncodable => Msg::EpgNotEncodable,
+            TextIssue::Language => Msg::EpgLanguage,
+            TextIssue::Placeholder => Msg::EpgPlaceholder,
+        }
+    }
+}
+
+pub struct TextError<'a> {
+    pub issue: TextIssue,
+    pub event: &'a Event,
+    // eventName, shortDescription, longDescription or languageCode
+    pub field: &'static str,
+    pub detail: String,
+}
+
+pub fn check<'a>(defines: &'a [Define], rules: &EpgRules) -> Vec<TextError<'a>> {
+    let mut errors = Vec::new();
+    for define in defines {
+        let Define::siEvent(event) = define else {
+            continue;
+        };
+        let mut push = |issue, field, detail: String| {
+            errors.push(TextError {
+                issue,
+                event,
+                field,
+                detail,
+            })
+        };
+        // without a siStandard every required text is missing
+        let Some(description) = event.get_description() else {
+            for field in TEXT_FIELDS.into_iter().filter(|field| rules.is_required(field)) {
+                push(TextIssue::Missing, field, String::new());
+            }
+            continue;
+        };
+
+        let code = description.get_languagecode();
+        if let Some(language) = rules.language(&event.get_serviceid()) {
+            if !code.trim().eq_ignore_ascii_case(language) {
+                push(
+                    TextIssue::Language,
+                    "languageCode",
+                    format!("{} ≠ {}", code, language),
+                );
+            }
+        }
+        for (field, text) in description.get_texts() {
+            if text.trim().is_empty() {
+                if rules.is_required(field) {
+                    push(TextIssue::Missing, field, String::new());
+                }
+                continue;
+            }
+            let length = text.chars().count();
+            if let Some(max) = rules.max_length(field).filter(|max| length > *max) {
+                push(TextIssue::TooLong, field, format!("{} > {}", length, max));
+            }
+            let unencodable = rules.unencodable(text);
+            if !unencodable.is_empty() {
+                push(TextIssue::NotEncodable, field, unencodable);
+            }
+            if let Some(placeholder) = rules.placeholder(text) {
+                push(TextIssue::Placeholder, field, placeholder.to_string());
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    fn issues(pts: &Pts) -> Vec<(String, TextIssue, &'static str, String)> {
+        let defines = pts.defines();
+        check(&defines, &EpgRules::default_rules())
+            .iter()
+            .map(|error| {
+                let title = error.event.get_title();
+                (title, error.issue, error.field, error.detail.clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn complete_texts() {
+        let mut pts = Pts::new();
+        pts.si("Film", "18:00:00", minutes(90), "P100").describe(
+            "deu",
+            "Film",
+            "Ein Film",
+            "Ein langer Film über Ärger",
+        );
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn texts_with_issues() {
+        let long = "x".repeat(81);
+        let mut pts = Pts::new();
+        pts.si("Film", "18:00:00", minutes(90), "P100")
+            .describe("eng", &long, " ", "")
+            .si("Serie", "19:30:00", minutes(30), "P200")
+            .describe("DEU", "Serie – Folge 1", "Text folgt", "Lorem ipsum")
+            .si("Nachrichten", "20:00:00", minutes(15), "P300")
+            .bare();
+        let issue = |title: &str, issue, field, detail: &str| {
+            (title.to_string(), issue, field, detail.to_string())
+        };
+        assert_eq!(
+            issues(&pts),
+            [
+                issue("Film", TextIssue::Language, "languageCode", "eng ≠ deu"),
+                issue("Film", TextIssue::TooLong, "eventName", "81 > 80"),
+                issue("Film", TextIssue::Missing, "shortDescription", ""),
+                issue("Serie", TextIssue::NotEncodable, "eventName", "–"),
+                issue(
+                    "Serie",
+                    TextIssue::Placeholder,
+                    "shortDescription",
+                    "Text folgt"
+                ),
+                issue(
+                    "Serie",
+                    TextIssue::Placeholder,
+                    "longDescription",
+                    "Lorem ipsum"
+                ),
+                issue("Nachrichten", TextIssue::Missing, "eventName", ""),
+                issue("Nachrichten", TextIssue::Missing, "shortDescription", ""),
+            ]
+        );
+    }
+}
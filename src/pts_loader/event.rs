@@ -1,10 +1,11 @@
 use crate::commandline::commandline::Commandline;
+use crate::utils::messages::Msg;
 use crate::utils::take::Take;
 
 use super::define::SiError;
 use super::sistandard::*;
 use chrono::{DateTime, Duration, Local, Utc};
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -117,14 +118,27 @@ impl Event {
         self.title.to_string()
     }
 
+    // empty if the event has no contentId, see has_contentid
     pub fn get_contentid(&self) -> String {
-        self.contentid.clone().unwrap()
+        self.contentid.clone().unwrap_or_default()
+    }
+
+    pub fn has_contentid(&self) -> bool {
+        self.contentid.as_ref().is_some_and(|id| !id.trim().is_empty())
     }
 
     pub fn get_programid(&self) -> String {
         self.programid.clone()
     }
 
+    pub fn get_eventid(&self) -> String {
+        self.eventid.clone()
+    }
+
+    pub fn get_serviceid(&self) -> String {
+        self.serviceid.clone()
+    }
+
     pub fn get_starttime(&self) -> Option<DateTime<Utc>> {
         Some(self.starttime)
     }
@@ -147,6 +161,10 @@ impl Event {
         }
     }
 
+    pub fn get_description(&self) -> Option<&Description> {
+        self.sistandard.as_ref().map(|sistandard| sistandard.get_description())
+    }
+
     pub fn get_dendtime(&self) -> Option<DateTime<Utc>> {
         match &self.sistandard {
             None => None,
@@ -173,9 +191,9 @@ impl Event {
         }
     }
 
-    pub fn print_vaevent_verbose(&self, time_error: &bool, utc: bool, fps: Option<i64>) {
+    pub fn vaevent_row(&self, time_error: &bool, utc: bool, fps: Option<i64>) -> Vec<ColoredString> {
         let contentid = self.get_contentid();
-        let mut title = self.title_to_string();
+        let mut title = self.get_title();
         if title == " -  UHD1_WERBUNG-01" {
             title = "Werbung".to_string();
         } else if self.get_contentid() == "cb7a119f84cb7b117b1b" {
@@ -183,16 +201,15 @@ impl Event {
         } else if self.get_contentid() == "392654926764849cd5dc" {
             title = "Pausentafel ".to_string();
         }
-        println!(
-            "| {:30} | {:15} | {:23} | {:23} | {:12} | {:20} |",
-            title,
-            self.programid_to_string(),
+        vec![
+            title.clone().normal(),
+            self.programid_to_string().normal(),
             if *time_error {
                 self.starttime_to_string(utc, fps).red()
             } else {
                 self.starttime_to_string(utc, fps).red().clear()
             },
-            self.endtime_to_string(utc, fps),
+            self.endtime_to_string(utc, fps).normal(),
             if title == "Werbung" {
                 self.duration_to_string(fps).yellow()
             } else {
@@ -203,7 +220,7 @@ impl Event {
             } else {
                 contentid.red().clear()
             },
-        );
+        ]
     }
 
     pub fn fmt_event_verbose(&self, f: &mut fmt::Formatter<'_>, kind: &str) -> fmt::Result {
@@ -290,14 +307,14 @@ impl Event {
 
         let s = if !first {
             match **err {
-                SiError::Gap => format!("{} #<-- Time gap", starttime)
+                SiError::Gap => format!("{} #<-- {}", starttime, Msg::TimeGap)
                     .red()
                     .on_custom_color(colored::CustomColor {
                         r: 45,
                         g: 45,
                         b: 45,
                     }),
-                SiError::Overlap => format!("{} #<-- Time overlap", starttime)
+                SiError::Overlap => format!("{} #<-- {}", starttime, Msg::TimeOverlap)
                     .red()
                     .on_custom_color(colored::CustomColor {
                         r: 45,
@@ -311,14 +328,14 @@ impl Event {
         };
         let e = if first {
             match **err {
-                SiError::Gap => format!("{} #<-- Time gap", endtime).red().on_custom_color(
+                SiError::Gap => format!("{} #<-- {}", endtime, Msg::TimeGap).red().on_custom_color(
                     colored::CustomColor {
                         r: 45,
                         g: 45,
                         b: 45,
                     },
                 ),
-                SiError::Overlap => format!("{} #<-- Time overlap", endtime)
+                SiError::Overlap => format!("{} #<-- {}", endtime, Msg::TimeOverlap)
                     .red()
                     .on_custom_color(colored::CustomColor {
                         r: 45,
@@ -494,10 +511,6 @@ impl Event {
         format!("{}", self.programid)
     }
 
-    pub fn title_to_string(&self) -> String {
-        format!("{}", &self.title).take(30)
-    }
-
     pub fn print_si_event_verbose(
         &self,
         first: bool,
@@ -0,0 +1,274 @@
+// Small PTS schedules for the tests. Times are "hh:mm:ss" on 2024-05-01 UTC
+// or full "2024-05-01T18:00:00" timestamps, durations are milliseconds.
+// Not every test module uses every helper.
+#![allow(dead_code)]
+
+use crate::pts_loader::dataset::DataSet;
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, TimeZone, Utc};
+use std::sync::Once;
+
+pub fn at(time: &str) -> DateTime<Utc> {
+    let time = match time.len() {
+        8 => format!("2024-05-01T{}", time),
+        _ => time.to_string(),
+    };
+    NaiveDateTime::parse_from_str(&time, "%Y-%m-%dT%H:%M:%S")
+        .expect("a time like 18:00:00")
+        .and_utc()
+}
+
+// The tests with local times run in Europe/Berlin, which has summer time.
+// Every test sets the same zone, so the order they run in does not matter.
+pub fn berlin() {
+    static ZONE: Once = Once::new();
+    ZONE.call_once(|| std::env::set_var("TZ", "Europe/Berlin"));
+}
+
+// the utc timestamp for `at` of a local time, e.g. "2024-05-01T22:00:00"
+pub fn local(time: &str) -> String {
+    berlin();
+    Local
+        .from_local_datetime(&at(time).naive_utc())
+        .earliest()
+        .expect("a local time")
+        .with_timezone(&Utc)
+        .format("%Y-%m-%dT%H:%M:%S")
+        .to_string()
+}
+
+fn time_text(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+pub fn duration_text(ms: i64) -> String {
+    format!(
+        "{:02} {:02}:{:02}:{:02}.{:03}",
+        ms / 86_400_000,
+        ms / 3_600_000 % 24,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+pub struct Description {
+    pub language: &'static str,
+    pub name: String,
+    pub short: String,
+    pub long: String,
+}
+
+struct Fixture {
+    kind: &'static str,
+    title: String,
+    programid: String,
+    contentid: String,
+    start: DateTime<Utc>,
+    duration: i64,
+    offset: Option<i64>,
+    // displayedStart and displayedDuration of siEvents
+    displayed: (DateTime<Utc>, i64),
+    description: Option<Description>,
+    // siEvents without a siStandard
+    bare: bool,
+}
+
+#[derive(Default)]
+pub struct Pts {
+    fixtures: Vec<Fixture>,
+}
+
+impl Pts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(
+        &mut self,
+        kind: &'static str,
+        title: &str,
+        contentid: &str,
+        start: &str,
+        ms: i64,
+        programid: &str,
+    ) -> &mut Self {
+        self.fixtures.push(Fixture {
+            kind,
+            title: title.to_string(),
+            programid: programid.to_string(),
+            contentid: contentid.to_string(),
+            start: at(start),
+            duration: ms,
+            offset: None,
+            displayed: (at(start), ms),
+            description: None,
+            bare: false,
+        });
+        self
+    }
+
+    pub fn va(
+        &mut self,
+        title: &str,
+        contentid: &str,
+        start: &str,
+        ms: i64,
+        programid: &str,
+    ) -> &mut Self {
+        self.push("vaEvent", title, contentid, start, ms, programid)
+    }
+
+    // segment `part` of the film P100, the first is " - 001 Film" with the
+    // content id 1572515-971182 and the programId P100-1
+    pub fn film(&mut self, part: i64, start: &str, ms: i64) -> &mut Self {
+        let title = format!(" - {:03} Film", part);
+        let contentid = format!("1572515-{}", 971181 + part);
+        self.va(&title, &contentid, start, ms, &format!("P100-{}", part))
+    }
+
+    // a block of a 10 second Dranbleiben, `ms` of commercials and a 10 second
+    // Bald weiter
+    pub fn break_at(&mut self, start: &str, ms: i64) -> &mut Self {
+        let time = |offset: i64| {
+            let time = at(start) + TimeDelta::milliseconds(offset);
+            time.format("%Y-%m-%dT%H:%M:%S").to_string()
+        };
+        self.va("Dranbleiben", "3ecea6b8084f35c2634d", start, 10_000, "D1")
+            .va("Spot", "UHD1_WERBUNG-01", &time(10_000), ms, "W1")
+            .va("Bald weiter", "02e9d82071c7735de5cd", &time(10_000 + ms), 10_000, "E1")
+    }
+
+    pub fn logo(&mut self, contentid: &str, start: &str, ms: i64, programid: &str) -> &mut Self {
+        self.push("logoEvent", "logo", contentid, start, ms, programid)
+    }
+
+    pub fn layout(&mut self, contentid: &str, start: &str, ms: i64, programid: &str) -> &mut Self {
+        self.push("layoutEvent", "layout", contentid, start, ms, programid)
+    }
+
+    pub fn si(&mut self, title: &str, start: &str, ms: i64, programid: &str) -> &mut Self {
+        self.push("siEvent", title, "SI", start, ms, programid)
+    }
+
+    // the displayed times of the last siEvent
+    pub fn displayed(&mut self, start: &str, ms: i64) -> &mut Self {
+        self.last().displayed = (at(start), ms);
+        self
+    }
+
+    pub fn offset(&mut self, ms: i64) -> &mut Self {
+        self.last().offset = Some(ms);
+        self
+    }
+
+    pub fn describe(
+        &mut self,
+        language: &'static str,
+        name: &str,
+        short: &str,
+        long: &str,
+    ) -> &mut Self {
+        self.last().description = Some(Description {
+            language,
+            name: name.to_string(),
+            short: short.to_string(),
+            long: long.to_string(),
+        });
+        self
+    }
+
+    // the last siEvent without a siStandard
+    pub fn bare(&mut self) -> &mut Self {
+        self.last().bare = true;
+        self
+    }
+
+    fn last(&mut self) -> &mut Fixture {
+        self.fixtures.last_mut().expect("an event")
+    }
+
+    // CRLF line endings and tabs like the files of the playout system
+    pub fn text(&self) -> String {
+        let escape = |s: &str| {
+            s.replace('&', "&amp;")
+                .replace('"', "&quot;")
+                .replace('<', "&lt;")
+        };
+        let mut lines = vec![
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>".to_string(),
+            "<PTS>".to_string(),
+            "\t<eventCommands>".to_string(),
+        ];
+        for (i, f) in self.fixtures.iter().enumerate() {
+            lines.push("\t\t<DEFINE>".to_string());
+            lines.push(format!("\t\t\t<{}", f.kind));
+            let mut attributes = vec![
+                ("eventId", (1001 + i).to_string()),
+                ("serviceId", "UHD1".to_string()),
+                ("programId", escape(&f.programid)),
+                ("startTime", time_text(f.start)),
+                ("title", escape(&f.title)),
+                ("duration", duration_text(f.duration)),
+                ("contentId", escape(&f.contentid)),
+            ];
+            if let Some(offset) = f.offset {
+                attributes.push(("offset", duration_text(offset)));
+            }
+            for (name, value) in attributes {
+                lines.push(format!("\t\t\t\t{}=\"{}\"", name, value));
+            }
+            lines.last_mut().unwrap().push('>');
+            if f.kind == "siEvent" && !f.bare {
+                let (language, name, short, long) = match &f.description {
+                    Some(d) => (
+                        d.language,
+                        d.name.as_str(),
+                        d.short.as_str(),
+                        d.long.as_str(),
+                    ),
+                    None => ("deu", f.title.as_str(), "kurz", "Text"),
+                };
+                lines.extend([
+                    "\t\t\t\t<siStandard".to_string(),
+                    format!("\t\t\t\t\tdisplayedStart=\"{}\"", time_text(f.displayed.0)),
+                    format!(
+                        "\t\t\t\t\tdisplayedDuration=\"{}\">",
+                        duration_text(f.displayed.1)
+                    ),
+                    "\t\t\t\t\t<siDescriptions>".to_string(),
+                    "\t\t\t\t\t\t<description".to_string(),
+                    format!("\t\t\t\t\t\t\tlanguageCode=\"{}\"", language),
+                    format!("\t\t\t\t\t\t\teventName=\"{}\"", escape(name)),
+                    format!("\t\t\t\t\t\t\tshortDescription=\"{}\"", escape(short)),
+                    format!("\t\t\t\t\t\t\tlongDescription=\"{}\">", escape(long)),
+                    "\t\t\t\t\t\t</description>".to_string(),
+                    "\t\t\t\t\t</siDescriptions>".to_string(),
+                    "\t\t\t\t</siStandard>".to_string(),
+                ]);
+            }
+            lines.push(format!("\t\t\t</{}>", f.kind));
+            lines.push("\t\t</DEFINE>".to_string());
+        }
+        lines.push("\t</eventCommands>".to_string());
+        lines.push("</PTS>".to_string());
+        lines.join("\r\n") + "\r\n"
+    }
+
+    pub fn dataset(&self) -> DataSet {
+        DataSet::init_from_data(self.text()).expect("a valid schedule")
+    }
+
+    pub fn defines(&self) -> Vec<Define> {
+        self.dataset().defines().to_vec()
+    }
+}
+
+pub fn titles<'a>(events: impl IntoIterator<Item = &'a Event>) -> Vec<String> {
+    events.into_iter().map(|event| event.get_title()).collect()
+}
+
+pub fn minutes(n: i64) -> i64 {
+    TimeDelta::minutes(n).num_milliseconds()
+}
@@ -0,0 +1,203 @@
+use crate::pts_loader::define::Define;
+use crate::utils::glob;
+use crate::utils::messages::Msg;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdentifierIssue {
+    // the same eventId for the same content more than once
+    DuplicateEventId,
+    // one eventId for different content
+    EventIdReused,
+    MalformedProgramId,
+    MixedServiceId,
+    MissingContentId,
+}
+
+impl IdentifierIssue {
+    pub fn msg(self) -> Msg {
+        match self {
+            IdentifierIssue::DuplicateEventId => Msg::IdDuplicateEventId,
+            IdentifierIssue::EventIdReused => Msg::IdEventIdReused,
+            IdentifierIssue::MalformedProgramId => Msg::IdMalformedProgramId,
+            IdentifierIssue::MixedServiceId => Msg::IdMixedServiceId,
+            IdentifierIssue::MissingContentId => Msg::IdMissingContentId,
+        }
+    }
+}
+
+pub struct IdentifierError<'a> {
+    pub issue: IdentifierIssue,
+    pub define: &'a Define,
+    // the other content ids, the expected serviceId, ...
+    pub detail: String,
+}
+
+fn well_formed(id: &str, pattern: Option<&str>) -> bool {
+    if id.is_empty()
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+    {
+        return false;
+    }
+    match pattern {
+        None => true,
+        Some(pattern) => glob::matches(pattern, id),
+    }
+}
+
+pub fn check<'a>(defines: &'a [Define], pattern: Option<&str>) -> Vec<IdentifierError<'a>> {
+    let mut errors = Vec::new();
+
+    let mut by_eventid: Vec<(String, Vec<&Define>)> = Vec::new();
+    for define in defines {
+        let id = define.get_event().get_eventid();
+        match by_eventid.iter_mut().find(|(x, _)| *x == id) {
+            Some((_, group)) => group.push(define),
+            None => by_eventid.push((id, vec![define])),
+        }
+    }
+    for (_, group) in by_eventid.iter().filter(|(_, group)| group.len() > 1) {
+        let mut contents: Vec<String> = Vec::new();
+        for define in group {
+            let content = define.get_event().get_contentid();
+            if !contents.contains(&content) {
+                contents.push(content);
+            }
+        }
+        let issue = if contents.len() > 1 {
+            IdentifierIssue::EventIdReused
+        } else {
+            IdentifierIssue::DuplicateEventId
+        };
+        for define in &group[1..] {
+            errors.push(IdentifierError {
+                issue,
+                define,
+                detail: format!("{}x: {}", group.len(), contents.join(", ")),
+            });
+        }
+    }
+
+    // the serviceId most events use is the one of the file
+    let mut services: Vec<(String, usize)> = Vec::new();
+    for define in defines {
+        let id = define.get_event().get_serviceid();
+        match services.iter_mut().find(|(x, _)| *x == id) {
+            Some((_, count)) => *count += 1,
+            None => services.push((id, 1)),
+        }
+    }
+    let service = services
+        .iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(id, _)| id.clone());
+
+    for define in defines {
+        let event = define.get_event();
+        if !well_formed(&event.get_programid(), pattern) {
+            errors.push(IdentifierError {
+                issue: IdentifierIssue::MalformedProgramId,
+                define,
+                detail: pattern.unwrap_or_default().to_string(),
+            });
+        }
+        if let Some(service) = &service {
+            if event.get_serviceid() != *service {
+                errors.push(IdentifierError {
+                    issue: IdentifierIssue::MixedServiceId,
+                    define,
+                    detail: service.clone(),
+                });
+            }
+        }
+        if let Define::vaEvent(event) = define {
+            if !event.has_contentid() {
+                errors.push(IdentifierError {
+                    issue: IdentifierIssue::MissingContentId,
+                    define,
+                    detail: String::new(),
+                });
+            }
+        }
+    }
+    errors.sort_by_key(|error| error.define.get_event().get_starttime());
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::dataset::DataSet;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    #[test]
+    fn well_formed_program_ids() {
+        assert!(well_formed("P100-1", None));
+        assert!(well_formed("a_b./c", None));
+        assert!(!well_formed("", None));
+        assert!(!well_formed("P100 1", None));
+        assert!(!well_formed("Pä100", None));
+        assert!(!well_formed("X100-1", Some("P*")));
+        assert!(well_formed("P100-1", Some("P###-#")));
+    }
+
+    #[test]
+    fn identifier_issues() {
+        let mut pts = Pts::new();
+        pts.va("Film", "1572515-971182", "18:00:00", minutes(10), "P100-1")
+            .va("Film", "1572515-971182", "18:10:00", minutes(10), "P100-2")
+            .va("Serie", "1600000-100001", "18:20:00", minutes(10), "P200 1")
+            .va("Serie", " ", "18:30:00", minutes(10), "P200-2")
+            .logo("HDPLUHD_LOGO_3", "18:40:00", minutes(10), "P300");
+        // 1002 repeats 1001, 1003 reuses it for other content, 1005 is on
+        // another service
+        let text = pts
+            .text()
+            .replace("eventId=\"1002\"", "eventId=\"1001\"")
+            .replace("eventId=\"1003\"", "eventId=\"1001\"")
+            .replace(
+                "eventId=\"1005\"\r\n\t\t\t\tserviceId=\"UHD1\"",
+                "eventId=\"1005\"\r\n\t\t\t\tserviceId=\"UHD2\"",
+            );
+        let dataset = DataSet::init_from_data(text).unwrap();
+        let issues: Vec<(String, IdentifierIssue, String)> = check(dataset.defines(), None)
+            .iter()
+            .map(|error| {
+                let id = error.define.get_event().get_programid();
+                (id, error.issue, error.detail.clone())
+            })
+            .collect();
+        let reused = "3x: 1572515-971182, 1600000-100001";
+        assert_eq!(
+            issues,
+            [
+                (
+                    "P100-2".to_string(),
+                    IdentifierIssue::EventIdReused,
+                    reused.to_string()
+                ),
+                (
+                    "P200 1".to_string(),
+                    IdentifierIssue::EventIdReused,
+                    reused.to_string()
+                ),
+                (
+                    "P200 1".to_string(),
+                    IdentifierIssue::MalformedProgramId,
+                    String::new()
+                ),
+                (
+                    "P200-2".to_string(),
+                    IdentifierIssue::MissingContentId,
+                    String::new()
+                ),
+                (
+                    "P300".to_string(),
+                    IdentifierIssue::MixedServiceId,
+                    "UHD1".to_string()
+                ),
+            ]
+        );
+    }
+}
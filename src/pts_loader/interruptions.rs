@@ -0,0 +1,202 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::{overlap, span, Span};
+use crate::pts_loader::special_event::SpecialEvent;
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum InterruptionIssue {
+    // the programme ran shorter than the minimum before it was interrupted
+    ShortSegment,
+    // the break starts too soon after the previous one
+    BreaksTooClose,
+}
+
+pub struct InterruptionError<'a> {
+    pub issue: InterruptionIssue,
+    // the first segment of the programme
+    pub event: &'a Event,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    // programme time for ShortSegment, break to break for BreaksTooClose
+    pub length: i64,
+}
+
+// " - 001 Film" and " - 00 Film" are the segments of a programme
+pub fn is_segment(event: &Event) -> bool {
+    let title = event.get_title();
+    let title = title.trim_start();
+    title
+        .strip_prefix('-')
+        .map(str::trim_start)
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| c.is_ascii_digit())
+}
+
+// "P100-1" and "P100-2" are segments of "P100"
+pub fn programme_id(event: &Event) -> String {
+    let id = event.get_programid();
+    match id.rsplit_once('-') {
+        Some((prefix, _)) if !prefix.is_empty() => prefix.to_string(),
+        _ => id,
+    }
+}
+
+// Rebuilds the programmes from their segments and measures the programme
+// time before every break within a programme (`min_segment`) and the time
+// from one break to the next (`break_interval`), both in milliseconds.
+pub fn check<'a>(
+    defines: &'a [Define],
+    special_events: &[SpecialEvent<'a>],
+    min_segment: i64,
+    break_interval: i64,
+) -> Vec<InterruptionError<'a>> {
+    let mut breaks: Vec<Span> = special_events
+        .iter()
+        .filter_map(|special_event| special_event.get_span())
+        .collect();
+    breaks.sort();
+    let in_break = |s: Span| breaks.iter().any(|b| overlap(*b, s).is_some());
+
+    let mut programmes: Vec<(String, Vec<(&Event, Span)>)> = Vec::new();
+    for define in defines {
+        let Define::vaEvent(event) = define else {
+            continue;
+        };
+        let Some(s) = span(event) else {
+            continue;
+        };
+        if !is_segment(event) || in_break(s) {
+            continue;
+        }
+        let id = programme_id(event);
+        match programmes.iter_mut().find(|(p, _)| *p == id) {
+            Some((_, segments)) => segments.push((event, s)),
+            None => programmes.push((id, vec![(event, s)])),
+        }
+    }
+
+    let mut errors = Vec::new();
+    for (_, mut segments) in programmes {
+        segments.sort_by_key(|(_, s)| *s);
+        let first = segments[0].0;
+        let programme = (segments[0].1 .0, segments[segments.len() - 1].1 .1);
+        let interruptions: Vec<Span> = breaks
+            .iter()
+            .filter(|b| programme.0 < b.0 && b.1 < programme.1)
+            .copied()
+            .collect();
+
+        let mut position = programme.0;
+        for (i, b) in interruptions.iter().enumerate() {
+            let length: i64 = segments
+                .iter()
+                .filter_map(|(_, s)| overlap(*s, (position, b.0)))
+                .map(|(from, to)| (to - from).num_milliseconds())
+                .sum();
+            if length < min_segment {
+                errors.push(InterruptionError {
+                    issue: InterruptionIssue::ShortSegment,
+                    event: first,
+                    start: position,
+                    end: b.0,
+                    length,
+                });
+            }
+            if let Some(previous) = i.checked_sub(1).map(|j| interruptions[j]) {
+                let length = (b.0 - previous.0).num_milliseconds();
+                if length < break_interval {
+                    errors.push(InterruptionError {
+                        issue: InterruptionIssue::BreaksTooClose,
+                        event: first,
+                        start: previous.0,
+                        end: b.0,
+                        length,
+                    });
+                }
+            }
+            position = b.1;
+        }
+    }
+    errors.sort_by_key(|error| error.start);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::block;
+    use crate::pts_loader::fixtures::{at, minutes, Pts};
+    use crate::utils::block_rules::BlockRules;
+
+    fn event(title: &str, programid: &str) -> Event {
+        let mut pts = Pts::new();
+        pts.va(title, "1572515-971182", "18:00:00", minutes(1), programid);
+        pts.defines()[0].get_event().clone()
+    }
+
+    // a film of 55 minutes, interrupted at 18:10 and 18:40 for 5 minutes
+    fn film() -> Pts {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(10))
+            .break_at("18:10:00", minutes(4) + 40_000)
+            .film(2, "18:15:00", minutes(25))
+            .break_at("18:40:00", minutes(4) + 40_000)
+            .film(3, "18:45:00", minutes(15))
+            .va(
+            " - 001 Serie",
+            "1600000-100001",
+            "19:00:00",
+            minutes(5),
+            "P200-1",
+        );
+        pts
+    }
+
+    fn issues(pts: &Pts, min_segment: i64, interval: i64) -> Vec<(bool, Span, i64)> {
+        let defines = pts.defines();
+        let (special_events, _) = block::parse(&defines, &BlockRules::default_rules());
+        check(
+            &defines,
+            &special_events,
+            minutes(min_segment),
+            minutes(interval),
+        )
+        .iter()
+        .map(|error| {
+            let short = error.issue == InterruptionIssue::ShortSegment;
+            (short, (error.start, error.end), error.length)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn segments_are_recognised_by_their_title() {
+        assert!(is_segment(&event(" - 001 Film", "P100-1")));
+        assert!(is_segment(&event(" - 00 Film", "P100-1")));
+        assert!(is_segment(&event("-1 Film", "P100-1")));
+        assert!(!is_segment(&event("Film - 001", "P100-1")));
+        assert!(!is_segment(&event(" - Film", "P100-1")));
+    }
+
+    #[test]
+    fn programme_id_without_the_segment_number() {
+        assert_eq!(programme_id(&event("Film", "P100-1")), "P100");
+        assert_eq!(programme_id(&event("Film", "P-100-12")), "P-100");
+        assert_eq!(programme_id(&event("Film", "P100")), "P100");
+        assert_eq!(programme_id(&event("Film", "-1")), "-1");
+    }
+
+    #[test]
+    fn short_first_segment() {
+        let expected = (true, (at("18:00:00"), at("18:10:00")), minutes(10));
+        assert_eq!(issues(&film(), 20, 30), [expected]);
+    }
+
+    #[test]
+    fn breaks_too_close() {
+        let expected = (false, (at("18:10:00"), at("18:40:00")), minutes(30));
+        assert_eq!(issues(&film(), 10, 31), [expected]);
+        assert!(issues(&film(), 10, 30).is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::commandline::commandline::Commandline;
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::{merge, overlap, programme_at, programmes, span, uncovered, Span};
+use crate::pts_loader::special_event::{logo_free, SpecialEvent};
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogoIssue {
+    // the whole segment runs without a logo
+    Missing,
+    LateStart,
+    EarlyEnd,
+    Gap,
+    IntoBreak,
+}
+
+pub struct LogoCoverageError<'a> {
+    pub issue: LogoIssue,
+    pub programme: Option<&'a Event>,
+    // the first segment of the stretch or, for IntoBreak, the break
+    // material the logo runs into
+    pub event: &'a Event,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl LogoCoverageError<'_> {
+    pub fn duration(&self) -> i64 {
+        (self.end - self.start).num_milliseconds()
+    }
+}
+
+// The vaEvents outside of the breaks are the segments of the programmes,
+// contiguous segments must be covered by logoEvents from the first to the
+// last frame, while breaks and material aired without a logo must not be.
+// Sendepausen run without a logo.
+pub fn check<'a>(
+    defines: &'a [Define],
+    special_events: &[SpecialEvent<'a>],
+    cmd: &Commandline,
+) -> Vec<LogoCoverageError<'a>> {
+    let breaks: Vec<Span> = merge(
+        special_events
+            .iter()
+            .filter_map(|special_event| special_event.get_span())
+            .collect(),
+    );
+    let logos: Vec<Span> = merge(
+        defines
+            .iter()
+            .filter_map(|define| match define {
+                Define::logoEvent(event) => span(event),
+                _ => None,
+            })
+            .collect(),
+    );
+    let programmes = programmes(defines);
+    let programme = |time| programme_at(&programmes, time);
+
+    let events = defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::vaEvent(event) => span(event).map(|s| (event, s)),
+            _ => None,
+        })
+        .filter(|(_, s)| !programme(s.0).is_some_and(|p| p.get_title().contains("Sendepause")));
+    let in_break = |s: Span| breaks.iter().any(|b| overlap(*b, s).is_some());
+    let commercial = |event: &Event| event.get_contentid().contains("WERBUNG");
+    let (segments, without_logo): (Vec<_>, Vec<_>) = events
+        .partition(|(event, s)| !in_break(*s) && !logo_free(event, cmd) && !commercial(event));
+
+    // contiguous segments are one stretch, the logo may run through
+    let mut stretches: Vec<(&Event, Span)> = Vec::new();
+    for (event, s) in segments {
+        match stretches.last_mut() {
+            Some((_, last)) if last.1 == s.0 => last.1 = s.1,
+            _ => stretches.push((event, s)),
+        }
+    }
+
+    let mut errors = Vec::new();
+    for (event, stretch) in stretches {
+        for (start, end) in uncovered(stretch, &logos) {
+            let issue = match (start == stretch.0, end == stretch.1) {
+                (true, true) => LogoIssue::Missing,
+                (true, false) => LogoIssue::LateStart,
+                (false, true) => LogoIssue::EarlyEnd,
+                (false, false) => LogoIssue::Gap,
+            };
+            errors.push(LogoCoverageError {
+                issue,
+                programme: programme(start),
+                event,
+                start,
+                end,
+            });
+        }
+    }
+
+    for (event, s) in without_logo {
+        for logo in &logos {
+            if let Some((start, end)) = overlap(*logo, s) {
+                errors.push(LogoCoverageError {
+                    issue: LogoIssue::IntoBreak,
+                    programme: programme(start),
+                    event,
+                    start,
+                    end,
+                });
+            }
+        }
+    }
+    errors.sort_by_key(|error| error.start);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::block;
+    use crate::pts_loader::fixtures::{at, minutes, Pts};
+    use crate::utils::block_rules::BlockRules;
+
+    // a film in two segments with a break between them
+    fn film(first_logo: (&str, i64), second_logo: (&str, i64)) -> Pts {
+        let mut pts = Pts::new();
+        pts.si("Film", "18:00:00", minutes(60), "P100")
+            .film(1, "18:00:00", minutes(20))
+            .logo("HDPLUHD_LOGO_3", first_logo.0, first_logo.1, "P100-1")
+            .va("Dranbleiben", "3ecea6b8084f35c2634d", "18:20:00", minutes(5), "D1")
+            .va("Spot UHD1_WERBUNG-01", "UHD1_WERBUNG-01", "18:25:00", 30_000, "W1")
+            .va("Bald weiter", "02e9d82071c7735de5cd", "18:25:30", minutes(5), "E1")
+            .film(2, "18:30:30", minutes(20))
+            .logo("HDPLUHD_LOGO_3", second_logo.0, second_logo.1, "P100-2")
+            .va("Trailer Serie", "5a3e0c3c1c8e4f3d9a77", "18:50:30", 30_000, "T1");
+        pts
+    }
+
+    fn issues(pts: &Pts) -> Vec<(LogoIssue, DateTime<Utc>, DateTime<Utc>)> {
+        let defines = pts.defines();
+        let (special_events, _) = block::parse(&defines, &BlockRules::default_rules());
+        let cmd = Commandline::from_args(&[]);
+        check(&defines, &special_events, &cmd)
+            .iter()
+            .map(|error| (error.issue, error.start, error.end))
+            .collect()
+    }
+
+    #[test]
+    fn segmented_programme_with_logo_is_covered() {
+        let pts = film(("18:00:00", minutes(20)), ("18:30:30", minutes(20)));
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn late_logo_on_a_segment() {
+        let pts = film(("18:00:00", minutes(20)), ("18:31:30", minutes(19)));
+        let expected = (LogoIssue::LateStart, at("18:30:30"), at("18:31:30"));
+        assert_eq!(issues(&pts), [expected]);
+    }
+
+    #[test]
+    fn logo_into_the_break_and_the_trailer() {
+        let pts = film(("18:00:00", minutes(21)), ("18:30:30", minutes(21)));
+        assert_eq!(
+            issues(&pts),
+            [
+                (LogoIssue::IntoBreak, at("18:20:00"), at("18:21:00")),
+                (LogoIssue::IntoBreak, at("18:50:30"), at("18:51:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_logo() {
+        let pts = film(("18:00:00", minutes(20)), ("19:00:00", minutes(1)));
+        let expected = (LogoIssue::Missing, at("18:30:30"), at("18:50:30"));
+        assert_eq!(issues(&pts), [expected]);
+    }
+}
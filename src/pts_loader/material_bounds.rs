@@ -0,0 +1,179 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::interruptions::is_segment;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OffsetIssue {
+    // the segment starts in the material before the previous one ends
+    Overlap,
+    // the segment starts in the material before the previous one starts
+    OutOfOrder,
+}
+
+pub struct OffsetError<'a> {
+    pub issue: OffsetIssue,
+    pub previous: &'a Event,
+    pub event: &'a Event,
+    // how much of the material is aired twice
+    pub overlap: i64,
+}
+
+// a segment and its tc-in and tc-out
+type Segment<'a> = (&'a Event, (i64, i64));
+
+// "1572515-971182" and "1572515-971183" are cut from the material "1572515"
+fn content_root(event: &Event) -> String {
+    let id = event.get_contentid();
+    match id.split_once('-') {
+        Some((root, _)) if !root.is_empty() => root.to_string(),
+        _ => id,
+    }
+}
+
+// The segments cut from one material, found by the root of their content id,
+// have offsets that follow each other in the order they are aired.
+pub fn check_offsets(defines: &[Define]) -> Vec<OffsetError<'_>> {
+    let mut materials: Vec<(String, Vec<Segment>)> = Vec::new();
+    for define in defines {
+        let Define::vaEvent(event) = define else {
+            continue;
+        };
+        let Some(tc) = event.get_tcin_tcout().filter(|_| is_segment(event)) else {
+            continue;
+        };
+        let root = content_root(event);
+        match materials.iter_mut().find(|(m, _)| *m == root) {
+            Some((_, segments)) => segments.push((event, tc)),
+            None => materials.push((root, vec![(event, tc)])),
+        }
+    }
+
+    let mut errors = Vec::new();
+    for (_, mut segments) in materials {
+        segments.sort_by_key(|(event, _)| event.get_starttime());
+        for pair in segments.windows(2) {
+            let ((previous, (previous_in, previous_out)), (event, (tcin, _))) = (pair[0], pair[1]);
+            let issue = if tcin < previous_in {
+                OffsetIssue::OutOfOrder
+            } else if tcin < previous_out {
+                OffsetIssue::Overlap
+            } else {
+                continue;
+            };
+            errors.push(OffsetError {
+                issue,
+                previous,
+                event,
+                overlap: previous_out.min(tcin + event.get_duration()) - tcin.max(previous_in),
+            });
+        }
+    }
+    errors.sort_by_key(|error| error.event.get_starttime());
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    fn issues(pts: &Pts) -> Vec<(String, OffsetIssue, String, i64)> {
+        let defines = pts.defines();
+        check_offsets(&defines)
+            .iter()
+            .map(|error| {
+                let overlap = error.overlap / 60_000;
+                (
+                    error.event.get_contentid(),
+                    error.issue,
+                    error.previous.get_contentid(),
+                    overlap,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn offsets_follow_each_other() {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(20))
+            .offset(0)
+            .film(2, "18:30:00", minutes(20))
+            .offset(minutes(20))
+            .film(3, "19:00:00", minutes(20))
+            .offset(minutes(45));
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn overlapping_and_reversed_segments_of_one_material() {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(20))
+            .offset(0)
+            .film(2, "18:30:00", minutes(20))
+            .offset(minutes(19))
+            // another programme id, still the same material
+            .va(
+                " - 003 Film",
+                "1572515-971184",
+                "19:00:00",
+                minutes(20),
+                "P101-1",
+            )
+            .offset(minutes(10));
+        assert_eq!(
+            issues(&pts),
+            [
+                (
+                    "1572515-971183".to_string(),
+                    OffsetIssue::Overlap,
+                    "1572515-971182".to_string(),
+                    1
+                ),
+                (
+                    "1572515-971184".to_string(),
+                    OffsetIssue::OutOfOrder,
+                    "1572515-971183".to_string(),
+                    11
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn other_material_of_the_same_programme_is_not_compared() {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(20))
+            .offset(minutes(30))
+            .va(
+                " - 002 Film",
+                "1600000-100001",
+                "18:30:00",
+                minutes(20),
+                "P100-2",
+            )
+            .offset(0)
+            // no segment, e.g. a trailer cut from the film
+            .va(
+                "Trailer Film",
+                "1572515-971190",
+                "18:50:00",
+                minutes(1),
+                "T1",
+            )
+            .offset(0);
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn roots_of_content_ids() {
+        let root = |id: &str| {
+            let mut pts = Pts::new();
+            pts.va("Film", id, "18:00:00", 1000, "P100");
+            content_root(pts.defines()[0].get_event())
+        };
+        assert_eq!(root("1572515-971182"), "1572515");
+        assert_eq!(root("1572515"), "1572515");
+        assert_eq!(root("-971182"), "-971182");
+    }
+}
@@ -0,0 +1,187 @@
+use crate::commandline::commandline::Commandline;
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::utils::block_rules::BlockRules;
+use crate::utils::fluid::Fluid;
+use crate::utils::messages::Msg;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaterialIssue {
+    // the content id is not in fluid
+    Unknown,
+    // the material was thrown away
+    InDustbin,
+    // no registration or no material id
+    Unregistered,
+}
+
+impl MaterialIssue {
+    pub fn msg(self) -> Msg {
+        match self {
+            MaterialIssue::Unknown => Msg::MaterialUnknown,
+            MaterialIssue::InDustbin => Msg::MaterialInDustbin,
+            MaterialIssue::Unregistered => Msg::MaterialUnregistered,
+        }
+    }
+}
+
+pub struct MaterialError<'a> {
+    pub issue: MaterialIssue,
+    pub event: &'a Event,
+    pub material_id: String,
+    pub filename: String,
+}
+
+fn is_yes(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "1" | "x" | "y" | "yes" | "j" | "ja" | "true" | "wahr"
+    )
+}
+
+fn is_no(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "0" | "-" | "n" | "no" | "nein" | "false" | "falsch" | "incomplete" | "unvollständig"
+    )
+}
+
+// commercials, the ids opening and closing a block and the ignored content
+// ids are not managed in fluid
+fn unmanaged(id: &str, block_rules: &BlockRules, cmd: &Commandline) -> bool {
+    id.contains("WERBUNG")
+        || block_rules.is_begin(id)
+        || block_rules.is_end(id)
+        || cmd
+            .get_content_ids_to_ignore()
+            .iter()
+            .any(|x| !x.is_empty() && id.contains(x.as_str()))
+}
+
+// Columns that are not in the fluid export are not checked. An empty
+// Registration says nothing and is kept, an empty MaterialId is reported.
+pub fn check<'a>(
+    defines: &'a [Define],
+    block_rules: &BlockRules,
+    fluid: &Fluid,
+    cmd: &Commandline,
+) -> Vec<MaterialError<'a>> {
+    let mut errors = Vec::new();
+    for define in defines {
+        let Define::vaEvent(event) = define else {
+            continue;
+        };
+        if !event.has_contentid() || unmanaged(&event.get_contentid(), block_rules, cmd) {
+            continue;
+        }
+        let Some(entry) = fluid.entry(event) else {
+            errors.push(MaterialError {
+                issue: MaterialIssue::Unknown,
+                event,
+                material_id: String::new(),
+                filename: String::new(),
+            });
+            continue;
+        };
+        let column = |name: &str| entry.get(name).cloned();
+        let issue = if column("InDustbin").is_some_and(|value| is_yes(&value)) {
+            MaterialIssue::InDustbin
+        } else if column("Registration").is_some_and(|value| is_no(&value))
+            || column("MaterialId").is_some_and(|value| value.trim().is_empty())
+        {
+            MaterialIssue::Unregistered
+        } else {
+            continue;
+        };
+        errors.push(MaterialError {
+            issue,
+            event,
+            material_id: column("MaterialId").unwrap_or_default(),
+            filename: column("Filename").unwrap_or_default(),
+        });
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    fn schedule() -> Pts {
+        let mut pts = Pts::new();
+        pts.va("Film", "1572515-971182", "18:00:00", minutes(20), "P100")
+            .break_at("18:20:00", minutes(4))
+            .va("Serie", "1600000-100001", "18:24:20", minutes(20), "P200")
+            .va("Doku", "1700000-100001", "18:44:20", minutes(20), "P300")
+            .va("Trenner", "e90dfb84e30edf611e32", "19:04:20", 10_000, "I1")
+            .va("Neu", "1800000-100001", "19:04:30", minutes(20), "P400")
+            .va("Kurz", "1900000-100001", "19:24:30", minutes(20), "P500")
+            .va("Leer", "2000000-100001", "19:44:30", minutes(20), "P600");
+        pts
+    }
+
+    fn fluid() -> Fluid {
+        Fluid::from_entries(&[
+            &[
+                ("ContentId", "1572515-971182"),
+                ("Registration", "ja"),
+                ("MaterialId", "M1"),
+                ("InDustbin", "nein"),
+            ],
+            &[
+                ("ContentId", "1600000-100001"),
+                ("Registration", "ja"),
+                ("MaterialId", "M2"),
+                ("InDustbin", "x"),
+            ],
+            &[
+                ("ContentId", "1700000-100001"),
+                ("Registration", "Nein"),
+                ("MaterialId", "M3"),
+            ],
+            // nothing known about the registration yet
+            &[
+                ("ContentId", "1900000-100001"),
+                ("Registration", " "),
+                ("MaterialId", "M5"),
+            ],
+            &[
+                ("ContentId", "2000000-100001"),
+                ("Registration", "ja"),
+                ("MaterialId", ""),
+            ],
+        ])
+    }
+
+    #[test]
+    fn unknown_binned_and_unregistered_material() {
+        let defines = schedule().defines();
+        let cmd = Commandline::from_args(&[]);
+        let issues: Vec<(String, MaterialIssue)> =
+            check(&defines, &BlockRules::default_rules(), &fluid(), &cmd)
+                .iter()
+                .map(|error| (error.event.get_title(), error.issue))
+                .collect();
+        assert_eq!(
+            issues,
+            [
+                ("Serie".to_string(), MaterialIssue::InDustbin),
+                ("Doku".to_string(), MaterialIssue::Unregistered),
+                ("Neu".to_string(), MaterialIssue::Unknown),
+                ("Leer".to_string(), MaterialIssue::Unregistered),
+            ]
+        );
+    }
+
+    #[test]
+    fn yes_and_no_values() {
+        assert!(is_yes(" Ja "));
+        assert!(is_yes("TRUE"));
+        assert!(!is_yes(""));
+        assert!(is_no("unvollständig"));
+        assert!(is_no("-"));
+        assert!(!is_no(""));
+        assert!(!is_no("  "));
+    }
+}
@@ -1,6 +1,25 @@
+pub mod advertising;
+pub mod compliance;
 pub mod dataset;
+pub mod day_coverage;
 pub mod define;
+pub mod epg_text;
 pub mod event;
+#[cfg(test)]
+pub mod fixtures;
+pub mod identifiers;
+pub mod interruptions;
+pub mod logo_coverage;
+pub mod material_bounds;
+pub mod material_status;
+pub mod reruns;
+pub mod sendepause;
+pub mod si_fix;
 pub mod sistandard;
+pub mod span;
 pub mod special_event;
+pub mod titles;
+pub mod trailer_balls;
+pub mod video_format;
+pub mod youth_protection;
 pub mod block;
@@ -0,0 +1,392 @@
+use crate::commandline::commandline::Commandline;
+use crate::pts_loader::dataset::local_time;
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::{overlap, programme_at, programmes, span, Span};
+use crate::pts_loader::special_event::SpecialEvent;
+use crate::utils::fluid::{Fluid, QueryType};
+use crate::utils::messages::Msg;
+use crate::utils::rerun_rules::{RerunRule, RerunRules};
+use crate::utils::table::{Column, Table};
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::Colorize;
+
+// One airing of a vaEvent, owned so airings of several files can be
+// counted together.
+pub struct Airing {
+    pub content_id: String,
+    pub title: String,
+    // the fluid Title and Class, needs --fluid
+    pub fluid_title: Option<String>,
+    pub class: Option<String>,
+    // start of the siEvent it is aired in
+    pub programme: Option<DateTime<Utc>>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+pub struct Reruns<'a> {
+    // the content id or the fluid title
+    pub key: String,
+    pub title: String,
+    pub class: Option<String>,
+    pub rule: Option<&'a RerunRule>,
+    pub airings: usize,
+    pub days: usize,
+    pub max_per_day: usize,
+    pub min_gap: i64,
+}
+
+impl Reruns<'_> {
+    pub fn too_close(&self) -> bool {
+        self.rule
+            .is_some_and(|rule| self.min_gap < rule.min_gap * 60_000)
+    }
+
+    pub fn too_often(&self) -> bool {
+        self.rule
+            .and_then(|rule| rule.max_per_day)
+            .is_some_and(|max| self.max_per_day > max)
+    }
+}
+
+// the vaEvents outside of the breaks
+pub fn airings(
+    defines: &[Define],
+    special_events: &[SpecialEvent<'_>],
+    rules: &RerunRules,
+    fluid: &Fluid,
+) -> Vec<Airing> {
+    let breaks: Vec<Span> = special_events
+        .iter()
+        .filter_map(|special_event| special_event.get_span())
+        .collect();
+    let programmes = programmes(defines);
+    defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::vaEvent(event) => span(event).map(|s| (event, s)),
+            _ => None,
+        })
+        .filter(|(event, s)| {
+            event.has_contentid()
+                && !rules.ignored(&event.get_contentid())
+                && !breaks.iter().any(|b| overlap(*b, *s).is_some())
+        })
+        .map(|(event, (start, end)): (&Event, Span)| Airing {
+            content_id: event.get_contentid(),
+            title: event.get_title(),
+            fluid_title: fluid.query(event, QueryType::Column(String::from("Title"))),
+            class: fluid.query(event, QueryType::Class),
+            programme: programme_at(&programmes, start).and_then(|p| p.get_starttime()),
+            start,
+            end,
+        })
+        .collect()
+}
+
+// Groups the airings by content id or by fluid title. By title the segments
+// of one programme are one airing.
+pub fn count<'a>(
+    airings: &[Airing],
+    by_title: bool,
+    rules: &'a RerunRules,
+    utc: bool,
+) -> Vec<Reruns<'a>> {
+    let mut groups: Vec<(String, Vec<&Airing>)> = Vec::new();
+    for airing in airings {
+        let key = if by_title {
+            match &airing.fluid_title {
+                Some(title) if !title.trim().is_empty() => title.clone(),
+                _ => continue,
+            }
+        } else {
+            airing.content_id.clone()
+        };
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(airing),
+            None => groups.push((key, vec![airing])),
+        }
+    }
+
+    let mut result = Vec::new();
+    for (key, mut group) in groups {
+        group.sort_by_key(|airing| airing.start);
+        // segments of one programme are different content in the same siEvent
+        let mut spans: Vec<(&Airing, Span)> = Vec::new();
+        for airing in &group {
+            match spans.last_mut() {
+                Some((last, s))
+                    if by_title
+                        && last.programme.is_some()
+                        && last.programme == airing.programme
+                        && last.content_id != airing.content_id =>
+                {
+                    *last = airing;
+                    s.1 = s.1.max(airing.end)
+                }
+                _ => spans.push((airing, (airing.start, airing.end))),
+            }
+        }
+        if spans.len() < 2 {
+            continue;
+        }
+
+        let mut days: Vec<(NaiveDate, usize)> = Vec::new();
+        for (_, (start, _)) in &spans {
+            let day = local_time(*start, utc).date();
+            match days.iter_mut().find(|(d, _)| *d == day) {
+                Some((_, count)) => *count += 1,
+                None => days.push((day, 1)),
+            }
+        }
+        let min_gap = spans
+            .windows(2)
+            .map(|w| (w[1].1 .0 - w[0].1 .1).num_milliseconds())
+            .min()
+            .unwrap_or_default();
+        let first = group[0];
+        result.push(Reruns {
+            key,
+            title: first.title.clone(),
+            class: first.class.clone(),
+            rule: rules.rule(&first.content_id, first.class.as_deref()),
+            airings: spans.len(),
+            days: days.len(),
+            max_per_day: days
+                .iter()
+                .map(|(_, count)| *count)
+                .max()
+                .unwrap_or_default(),
+            min_gap,
+        });
+    }
+    result.sort_by(|a, b| b.airings.cmp(&a.airings).then(a.key.cmp(&b.key)));
+    result
+}
+
+// prints the reruns by content id and, with --fluid, by title and returns
+// the number of rule violations
+pub fn print(airings: &[Airing], rules: &RerunRules, cmd: &Commandline) -> i64 {
+    let mut errors = 0;
+    for by_title in [false, true] {
+        let reruns = count(airings, by_title, rules, cmd.utc());
+        if reruns.is_empty() {
+            continue;
+        }
+        let mut columns = vec![
+            Column::new("contentid", Msg::ColContentId.text(), 20),
+            Column::new("title", Msg::ColTitle.text(), 30),
+            Column::new("class", Msg::ColClass.text(), 15),
+            Column::new("airings", Msg::ColAirings.text(), 8),
+            Column::new("days", Msg::ColDays.text(), 5),
+            Column::new("perday", Msg::ColPerDay.text(), 8),
+            Column::new("mingap", Msg::ColMinGap.text(), 13),
+            Column::new("rule", Msg::ColRule.text(), 20),
+            Column::new("issue", Msg::ColIssue.text(), 22),
+        ];
+        if by_title {
+            columns.remove(0);
+        }
+        let mut table = Table::new(columns);
+        if let Some(columns) = cmd.columns() {
+            table.select(&columns);
+        }
+        for reruns in &reruns {
+            let issue = if reruns.too_close() {
+                Msg::RerunTooClose.text()
+            } else if reruns.too_often() {
+                Msg::RerunTooOften.text()
+            } else {
+                ""
+            };
+            if !issue.is_empty() {
+                errors += 1;
+            }
+            let rule = reruns
+                .rule
+                .map(|rule| match rule.max_per_day {
+                    Some(max) => format!("{} {}m {}/d", rule.name, rule.min_gap, max),
+                    None => format!("{} {}m", rule.name, rule.min_gap),
+                })
+                .unwrap_or_default();
+            let per_day = reruns.max_per_day.to_string();
+            // airings of the same content overlap if the gap is negative
+            let min_gap = match reruns.min_gap {
+                gap if gap < 0 => format!("-{}", Event::a_duration_to_string(-gap, cmd.fps())),
+                gap => Event::a_duration_to_string(gap, cmd.fps()),
+            };
+            let mut row = vec![
+                reruns.key.normal(),
+                if by_title {
+                    reruns.key.normal()
+                } else {
+                    reruns.title.normal()
+                },
+                reruns.class.clone().unwrap_or_default().normal(),
+                reruns.airings.to_string().normal(),
+                reruns.days.to_string().normal(),
+                if reruns.too_often() {
+                    per_day.red()
+                } else {
+                    per_day.normal()
+                },
+                if reruns.too_close() {
+                    min_gap.red()
+                } else {
+                    min_gap.normal()
+                },
+                rule.normal(),
+                issue.red(),
+            ];
+            if by_title {
+                row.remove(0);
+            }
+            table.add_row(row);
+        }
+        if by_title {
+            println!("{}", Msg::RerunsByTitle);
+        } else {
+            println!("{}", Msg::RerunsByContentId);
+        }
+        table.print(cmd);
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::block;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+    use crate::utils::block_rules::BlockRules;
+
+    // a film in two segments, aired at 18:00 and at 22:00
+    fn schedule() -> Pts {
+        let mut pts = Pts::new();
+        for hour in ["18", "22"] {
+            let time = |minute: &str| format!("{}:{}:00", hour, minute);
+            pts.si("Film", &time("00"), minutes(60), "P100")
+                .film(1, &time("00"), minutes(20))
+                .va(
+                    "Dranbleiben",
+                    "3ecea6b8084f35c2634d",
+                    &time("20"),
+                    minutes(1),
+                    "D1",
+                )
+                .va("Spot", "UHD1_WERBUNG-01", &time("21"), minutes(8), "W1")
+                .va(
+                    "Bald weiter",
+                    "02e9d82071c7735de5cd",
+                    &time("29"),
+                    minutes(1),
+                    "E1",
+                )
+                .film(2, &time("30"), minutes(20))
+                .va("Füller", "5000000-000001", &time("50"), minutes(10), "F1");
+        }
+        pts
+    }
+
+    fn fluid() -> Fluid {
+        Fluid::from_entries(&[
+            &[
+                ("ContentId", "1572515"),
+                ("Title", "Film"),
+                ("Class", "Spielfilm"),
+            ],
+            &[
+                ("ContentId", "5000000"),
+                ("Title", "Füller"),
+                ("Class", "Füller"),
+            ],
+        ])
+    }
+
+    fn reruns(by_title: bool) -> Vec<(String, usize, i64, String, bool)> {
+        let defines = schedule().defines();
+        let (special_events, _) = block::parse(&defines, &BlockRules::default_rules());
+        let rules = RerunRules::default_rules();
+        let airings = airings(&defines, &special_events, &rules, &fluid());
+        count(&airings, by_title, &rules, true)
+            .iter()
+            .map(|reruns| {
+                (
+                    reruns.key.clone(),
+                    reruns.airings,
+                    reruns.min_gap / 60_000,
+                    reruns
+                        .rule
+                        .map(|rule| rule.name.clone())
+                        .unwrap_or_default(),
+                    reruns.too_close(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn breaks_and_ignored_content_are_no_airings() {
+        let defines = schedule().defines();
+        let (special_events, _) = block::parse(&defines, &BlockRules::default_rules());
+        let rules = RerunRules::default_rules();
+        let airings = airings(&defines, &special_events, &rules, &Fluid::init());
+        let ids: Vec<&str> = airings.iter().map(|a| a.content_id.as_str()).collect();
+        let once = ["1572515-971182", "1572515-971183", "5000000-000001"];
+        assert_eq!(ids, [once, once].concat());
+        assert!(airings.iter().all(|a| a.fluid_title.is_none()));
+    }
+
+    #[test]
+    fn reruns_by_content_id() {
+        assert_eq!(
+            reruns(false),
+            [
+                (
+                    "1572515-971182".to_string(),
+                    2,
+                    220,
+                    "Programme".to_string(),
+                    true
+                ),
+                (
+                    "1572515-971183".to_string(),
+                    2,
+                    220,
+                    "Programme".to_string(),
+                    true
+                ),
+                (
+                    "5000000-000001".to_string(),
+                    2,
+                    230,
+                    "Filler".to_string(),
+                    false
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn segments_of_a_programme_are_one_airing_by_title() {
+        assert_eq!(
+            reruns(true),
+            [
+                ("Film".to_string(), 2, 190, "Programme".to_string(), true),
+                ("Füller".to_string(), 2, 230, "Filler".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn first_matching_rule_applies() {
+        let rules = RerunRules::default_rules();
+        let name = |id, class| rules.rule(id, class).map(|rule| rule.name.as_str());
+        assert_eq!(name("1", Some(" nachtschleife ")), Some("Loop"));
+        assert_eq!(name("1", Some("Füller")), Some("Filler"));
+        assert_eq!(name("1", None), Some("Programme"));
+        assert!(rules.ignored("UHD1_WERBUNG-01"));
+        assert!(!rules.ignored("1572515-971182"));
+    }
+}
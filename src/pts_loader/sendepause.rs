@@ -0,0 +1,262 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::{merge, overlap, span, uncovered, Span};
+use crate::utils::messages::Msg;
+use crate::utils::sendepause_rules::SendepauseRules;
+use chrono::{DateTime, Utc};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SendepauseIssue {
+    // no loop material during the whole Sendepause
+    LoopMissing,
+    LoopStartsLate,
+    LoopEndsEarly,
+    LoopGap,
+    // other material during a Sendepause
+    NotLoop,
+    // loop material outside of a Sendepause
+    LoopOutside,
+    LoopTooLong,
+    // a logo or layout during a Sendepause
+    Forbidden,
+}
+
+impl SendepauseIssue {
+    pub fn msg(self) -> Msg {
+        match self {
+            SendepauseIssue::LoopMissing => Msg::PauseLoopMissing,
+            SendepauseIssue::LoopStartsLate => Msg::PauseLoopStartsLate,
+            SendepauseIssue::LoopEndsEarly => Msg::PauseLoopEndsEarly,
+            SendepauseIssue::LoopGap => Msg::PauseLoopGap,
+            SendepauseIssue::NotLoop => Msg::PauseNotLoop,
+            SendepauseIssue::LoopOutside => Msg::PauseLoopOutside,
+            SendepauseIssue::LoopTooLong => Msg::PauseLoopTooLong,
+            SendepauseIssue::Forbidden => Msg::PauseForbidden,
+        }
+    }
+}
+
+pub struct SendepauseError<'a> {
+    pub issue: SendepauseIssue,
+    // the Sendepause siEvent
+    pub pause: Option<&'a Event>,
+    // the vaEvent, logoEvent or layoutEvent at fault
+    pub event: Option<(&'a str, &'a Event)>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl SendepauseError<'_> {
+    pub fn duration(&self) -> i64 {
+        (self.end - self.start).num_milliseconds()
+    }
+}
+
+pub fn check<'a>(defines: &'a [Define], rules: &SendepauseRules) -> Vec<SendepauseError<'a>> {
+    let events: Vec<(&Define, Span)> = defines
+        .iter()
+        .filter_map(|define| span(define.get_event()).map(|s| (define, s)))
+        .collect();
+    let pauses: Vec<(&Event, Span)> = events
+        .iter()
+        .filter_map(|(define, s)| match define {
+            Define::siEvent(event) if rules.is_sendepause(event) => Some((event, *s)),
+            _ => None,
+        })
+        .collect();
+    let loops: Vec<(&Event, Span)> = events
+        .iter()
+        .filter_map(|(define, s)| match define {
+            Define::vaEvent(event) if rules.is_loop(event) => Some((event, *s)),
+            _ => None,
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    for (pause, p) in &pauses {
+        let covered = merge(loops.iter().filter_map(|(_, s)| overlap(*s, *p)).collect());
+        for (start, end) in uncovered(*p, &covered) {
+            let issue = match (start == p.0, end == p.1) {
+                (true, true) => SendepauseIssue::LoopMissing,
+                (true, false) => SendepauseIssue::LoopStartsLate,
+                (false, true) => SendepauseIssue::LoopEndsEarly,
+                (false, false) => SendepauseIssue::LoopGap,
+            };
+            errors.push(SendepauseError {
+                issue,
+                pause: Some(pause),
+                event: None,
+                start,
+                end,
+            });
+        }
+
+        for (define, s) in &events {
+            let Some((start, end)) = overlap(*s, *p) else {
+                continue;
+            };
+            let issue = match define {
+                Define::vaEvent(event) if !rules.is_loop(event) => SendepauseIssue::NotLoop,
+                _ if rules.is_forbidden(define.kind()) => SendepauseIssue::Forbidden,
+                _ => continue,
+            };
+            errors.push(SendepauseError {
+                issue,
+                pause: Some(pause),
+                event: Some((define.kind(), define.get_event())),
+                start,
+                end,
+            });
+        }
+    }
+
+    let pause_spans = merge(pauses.iter().map(|(_, s)| *s).collect());
+    for (event, s) in &loops {
+        let pause = pauses
+            .iter()
+            .find(|(_, p)| overlap(*p, *s).is_some())
+            .map(|(pause, _)| *pause);
+        for (start, end) in uncovered(*s, &pause_spans) {
+            errors.push(SendepauseError {
+                issue: SendepauseIssue::LoopOutside,
+                pause,
+                event: Some(("vaEvent", event)),
+                start,
+                end,
+            });
+        }
+        if rules
+            .max_loop
+            .is_some_and(|max| event.get_duration() > max * 60_000)
+        {
+            errors.push(SendepauseError {
+                issue: SendepauseIssue::LoopTooLong,
+                pause,
+                event: Some(("vaEvent", event)),
+                start: s.0,
+                end: s.1,
+            });
+        }
+    }
+    errors.sort_by_key(|error| error.start);
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{at, minutes, Pts};
+
+    fn issues(pts: &Pts, rules: &SendepauseRules) -> Vec<(SendepauseIssue, Span, String)> {
+        let defines = pts.defines();
+        check(&defines, rules)
+            .iter()
+            .map(|error| {
+                let title = error.event.map(|(_, e)| e.get_title()).unwrap_or_default();
+                (error.issue, (error.start, error.end), title)
+            })
+            .collect()
+    }
+
+    fn span(start: &str, end: &str) -> Span {
+        (at(start), at(end))
+    }
+
+    #[test]
+    fn loop_holes_and_material_during_the_pause() {
+        let mut pts = Pts::new();
+        pts.si("Sendepause", "02:00:00", minutes(180), "S1")
+            .va("Film", "1572515-971182", "01:00:00", minutes(65), "P100")
+            .va(
+                "Nachtschleife",
+                "7000000-000001",
+                "02:10:00",
+                minutes(50),
+                "L1",
+            )
+            .logo("HDPLUHD_LOGO_3", "02:30:00", minutes(1), "L1")
+            .va(
+                "Nachtschleife",
+                "7000000-000001",
+                "03:05:00",
+                minutes(115),
+                "L2",
+            )
+            .va("Loop", "7000000-000002", "05:00:00", minutes(10), "L3");
+        let rules = SendepauseRules::default_rules();
+        let title = |title: &str| title.to_string();
+        assert_eq!(
+            issues(&pts, &rules),
+            [
+                (
+                    SendepauseIssue::LoopStartsLate,
+                    span("02:00:00", "02:10:00"),
+                    title("")
+                ),
+                (
+                    SendepauseIssue::NotLoop,
+                    span("02:00:00", "02:05:00"),
+                    title("Film")
+                ),
+                (
+                    SendepauseIssue::Forbidden,
+                    span("02:30:00", "02:31:00"),
+                    title("logo")
+                ),
+                (
+                    SendepauseIssue::LoopGap,
+                    span("03:00:00", "03:05:00"),
+                    title("")
+                ),
+                (
+                    SendepauseIssue::LoopOutside,
+                    span("05:00:00", "05:10:00"),
+                    title("Loop")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_short_and_long_loops() {
+        let mut rules = SendepauseRules::default_rules();
+        rules.max_loop = Some(60);
+        rules.forbidden.clear();
+
+        let mut pts = Pts::new();
+        pts.si("Sendepause", "02:00:00", minutes(60), "S1").logo(
+            "HDPLUHD_LOGO_3",
+            "02:30:00",
+            minutes(1),
+            "L1",
+        );
+        assert_eq!(
+            issues(&pts, &rules),
+            [(
+                SendepauseIssue::LoopMissing,
+                span("02:00:00", "03:00:00"),
+                String::new()
+            )]
+        );
+
+        let mut pts = Pts::new();
+        pts.si("Sendepause", "01:00:00", minutes(120), "S1")
+            .va("Loop", "7000000-000002", "01:00:00", minutes(61), "L1")
+            .va("Loop", "7000000-000002", "02:01:00", minutes(58), "L2");
+        assert_eq!(
+            issues(&pts, &rules),
+            [
+                (
+                    SendepauseIssue::LoopTooLong,
+                    span("01:00:00", "02:01:00"),
+                    "Loop".to_string()
+                ),
+                (
+                    SendepauseIssue::LoopEndsEarly,
+                    span("02:59:00", "03:00:00"),
+                    String::new()
+                ),
+            ]
+        );
+    }
+}
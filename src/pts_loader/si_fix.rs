@@ -0,0 +1,334 @@
+use crate::commandline::commandline::{Commandline, FixStrategy};
+use crate::pts_loader::define::{create_time, Define, SiError};
+use crate::pts_loader::event::Event;
+use crate::pts_loader::span::{span, Span};
+use chrono::{DateTime, TimeDelta, Utc};
+
+pub struct Change<'a> {
+    pub event: &'a Event,
+    // the attribute of the siEvent, e.g. "displayedStart"
+    pub attribute: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+// the time both events meet at, None if the pair can not be closed this way
+fn meeting(
+    strategy: FixStrategy,
+    first: Span,
+    second: Span,
+    boundaries: &[DateTime<Utc>],
+) -> Option<DateTime<Utc>> {
+    let time = match strategy {
+        FixStrategy::ExtendPrevious => second.0,
+        FixStrategy::PullNext => first.1,
+        FixStrategy::SnapVa => {
+            let (low, high) = (first.1.min(second.0), first.1.max(second.0));
+            let distance = |time: DateTime<Utc>| {
+                if time < low {
+                    low - time
+                } else if time > high {
+                    time - high
+                } else {
+                    TimeDelta::zero()
+                }
+            };
+            boundaries
+                .iter()
+                .copied()
+                .filter(|time| first.0 < *time && *time < second.1)
+                .min_by_key(|time| (distance(*time), (*time - second.0).abs()))?
+        }
+    };
+    (first.0 < time && time < second.1).then_some(time)
+}
+
+fn time_text(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+// "00 01:30:00.000", the days in front
+fn duration_text(span: Span) -> String {
+    let ms = (span.1 - span.0).num_milliseconds();
+    format!(
+        "{:02} {:02}:{:02}:{:02}.{:03}",
+        ms / 86_400_000,
+        ms / 3_600_000 % 24,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+fn changes<'a>(
+    event: &'a Event,
+    attributes: (&'static str, &'static str),
+    old: Span,
+    new: Span,
+) -> Vec<Change<'a>> {
+    let mut changes = Vec::new();
+    if old.0 != new.0 {
+        changes.push(Change {
+            event,
+            attribute: attributes.0,
+            old: time_text(old.0),
+            new: time_text(new.0),
+        });
+    }
+    if old.1 - old.0 != new.1 - new.0 {
+        changes.push(Change {
+            event,
+            attribute: attributes.1,
+            old: duration_text(old),
+            new: duration_text(new),
+        });
+    }
+    changes
+}
+
+// Closes the gaps and overlaps print_si_errors reports. The second event
+// keeps its end, so every pair can be closed on its own. Returns the changed
+// attributes and the pairs the strategy can not close.
+pub fn plan<'a>(
+    defines: &'a [Define],
+    strategy: FixStrategy,
+    cmd: &Commandline,
+) -> (Vec<Change<'a>>, Vec<(&'a Event, &'a Event)>) {
+    let sievents: Vec<(&Define, Span, Option<Span>)> = defines
+        .iter()
+        .filter(|define| matches!(define, Define::siEvent(..)))
+        .filter_map(|define| {
+            let event = define.get_event();
+            let displayed = event.get_dstarttime().zip(event.get_dendtime());
+            span(event).map(|s| (define, s, displayed))
+        })
+        .collect();
+    let mut boundaries: Vec<DateTime<Utc>> = defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::vaEvent(event) => span(event),
+            _ => None,
+        })
+        .flat_map(|(start, end)| [start, end])
+        .collect();
+    boundaries.sort();
+    boundaries.dedup();
+
+    let mut spans: Vec<(Span, Option<Span>)> = sievents
+        .iter()
+        .map(|(_, s, displayed)| (*s, *displayed))
+        .collect();
+    let mut unfixed = Vec::new();
+    for (i, pair) in sievents.windows(2).enumerate() {
+        let SiError::SomeError(err, display_err) = pair[0].0.get_si_error(pair[1].0, cmd) else {
+            continue;
+        };
+        let mut fixed = true;
+        if *err != SiError::NoError {
+            match meeting(strategy, spans[i].0, spans[i + 1].0, &boundaries) {
+                Some(time) => {
+                    spans[i].0 = (spans[i].0 .0, time);
+                    spans[i + 1].0 = (time, spans[i + 1].0 .1);
+                }
+                None => fixed = false,
+            }
+        }
+        if *display_err != SiError::NoError {
+            match (spans[i].1, spans[i + 1].1) {
+                (Some(first), Some(second)) => {
+                    match meeting(strategy, first, second, &boundaries) {
+                        Some(time) => {
+                            spans[i].1 = Some((first.0, time));
+                            spans[i + 1].1 = Some((time, second.1));
+                        }
+                        None => fixed = false,
+                    }
+                }
+                _ => fixed = false,
+            }
+        }
+        if !fixed {
+            unfixed.push((pair[0].0.get_event(), pair[1].0.get_event()));
+        }
+    }
+
+    let mut result = Vec::new();
+    for ((define, s, displayed), (new, new_displayed)) in sievents.iter().zip(spans) {
+        let event = define.get_event();
+        result.extend(changes(event, ("startTime", "duration"), *s, new));
+        if let (Some(old), Some(new)) = (displayed, new_displayed) {
+            result.extend(changes(
+                event,
+                ("displayedStart", "displayedDuration"),
+                *old,
+                new,
+            ));
+        }
+    }
+    (result, unfixed)
+}
+
+// the byte range of the value of the attribute `name`
+fn attribute(element: &str, name: &str) -> Option<std::ops::Range<usize>> {
+    let pattern = format!("{}=\"", name);
+    let mut from = 0;
+    while let Some(found) = element[from..].find(&pattern) {
+        let begin = from + found;
+        let start = begin + pattern.len();
+        if element[..begin].ends_with(char::is_whitespace) {
+            let end = start + element[start..].find('"')?;
+            return Some(start..end);
+        }
+        from = start;
+    }
+    None
+}
+
+// Writes the new values into the siEvents of the original text, which are
+// found by their eventId and startTime. Everything else stays as it is.
+pub fn apply(text: &str, changes: &[Change]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(begin) = rest.find("<siEvent") {
+        let Some(length) = rest[begin..].find("</siEvent>") else {
+            break;
+        };
+        result.push_str(&rest[..begin]);
+        let mut element = rest[begin..begin + length].to_string();
+        rest = &rest[begin + length..];
+
+        let value = |name: &str| attribute(&element, name).map(|range| element[range].to_string());
+        let eventid = value("eventId");
+        let start = value("startTime").and_then(|value| create_time(&value));
+        let own = changes.iter().filter(|change| {
+            Some(change.event.get_eventid()) == eventid && change.event.get_starttime() == start
+        });
+        for change in own.collect::<Vec<_>>() {
+            if let Some(range) = attribute(&element, change.attribute) {
+                element.replace_range(range, &change.new);
+            }
+        }
+        result.push_str(&element);
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::dataset::DataSet;
+    use crate::pts_loader::fixtures::{at, minutes, Pts};
+
+    // "Nachrichten" ends 19:00, ten minutes before "Serie" starts
+    fn gap() -> Pts {
+        let mut pts = Pts::new();
+        pts.va("Film", "1572515-971182", "18:00:00", minutes(65), "P100")
+            .si("Nachrichten", "18:00:00", minutes(60), "P100")
+            .si("Serie", "19:10:00", minutes(50), "P200");
+        pts
+    }
+
+    fn fixed(strategy: FixStrategy, pts: &Pts) -> String {
+        let cmd = Commandline::from_args(&[]);
+        let defines = pts.defines();
+        let (changes, unfixed) = plan(&defines, strategy, &cmd);
+        assert!(unfixed.is_empty());
+        apply(&pts.text(), &changes)
+    }
+
+    #[test]
+    fn extend_previous_closes_a_gap() {
+        // only the duration of the first event, nothing else in the file changes
+        let mut expected = Pts::new();
+        expected
+            .va("Film", "1572515-971182", "18:00:00", minutes(65), "P100")
+            .si("Nachrichten", "18:00:00", minutes(70), "P100")
+            .si("Serie", "19:10:00", minutes(50), "P200");
+        assert_eq!(fixed(FixStrategy::ExtendPrevious, &gap()), expected.text());
+    }
+
+    #[test]
+    fn pull_next_closes_a_gap() {
+        let mut expected = Pts::new();
+        expected
+            .va("Film", "1572515-971182", "18:00:00", minutes(65), "P100")
+            .si("Nachrichten", "18:00:00", minutes(60), "P100")
+            .si("Serie", "19:00:00", minutes(60), "P200");
+        assert_eq!(fixed(FixStrategy::PullNext, &gap()), expected.text());
+    }
+
+    #[test]
+    fn snap_va_meets_at_the_end_of_the_va_event() {
+        let mut expected = Pts::new();
+        expected
+            .va("Film", "1572515-971182", "18:00:00", minutes(65), "P100")
+            .si("Nachrichten", "18:00:00", minutes(65), "P100")
+            .si("Serie", "19:05:00", minutes(55), "P200");
+        assert_eq!(fixed(FixStrategy::SnapVa, &gap()), expected.text());
+    }
+
+    #[test]
+    fn overlaps_and_displayed_times() {
+        let mut pts = Pts::new();
+        pts.si("Nachrichten", "18:00:00", minutes(70), "P100")
+            .displayed("18:00:00", minutes(60))
+            .si("Serie", "19:00:00", minutes(60), "P200");
+        let mut expected = Pts::new();
+        expected
+            .si("Nachrichten", "18:00:00", minutes(60), "P100")
+            .si("Serie", "19:00:00", minutes(60), "P200");
+        assert_eq!(fixed(FixStrategy::ExtendPrevious, &pts), expected.text());
+    }
+
+    #[test]
+    fn pairs_the_strategy_can_not_close() {
+        let cmd = Commandline::from_args(&[]);
+        let mut pts = Pts::new();
+        pts.si("Nachrichten", "18:00:00", minutes(60), "P100").si(
+            "Serie",
+            "19:10:00",
+            minutes(50),
+            "P200",
+        );
+        let defines = pts.defines();
+        // no vaEvent to snap to
+        let (changes, unfixed) = plan(&defines, FixStrategy::SnapVa, &cmd);
+        assert!(changes.is_empty());
+        let titles: Vec<_> = unfixed
+            .iter()
+            .map(|(first, second)| (first.get_title(), second.get_title()))
+            .collect();
+        assert_eq!(titles, [("Nachrichten".to_string(), "Serie".to_string())]);
+        assert_eq!(apply(&pts.text(), &changes), pts.text());
+    }
+
+    #[test]
+    fn durations_of_a_day_and_more() {
+        let day = |start: &str, end: &str| duration_text((at(start), at(end)));
+        assert_eq!(day("18:00:00", "19:30:00"), "00 01:30:00.000");
+        assert_eq!(
+            day("2024-05-01T00:00:00", "2024-05-02T02:00:00"),
+            "01 02:00:00.000"
+        );
+
+        let mut pts = Pts::new();
+        pts.si(
+            "Sendeschluss",
+            "2024-05-01T00:00:00",
+            minutes(20 * 60),
+            "P100",
+        )
+        .si("Testbild", "2024-05-02T02:00:00", minutes(60), "P200");
+        let text = fixed(FixStrategy::ExtendPrevious, &pts);
+        assert!(text.contains("duration=\"01 02:00:00.000\""));
+        // and the fixed file loads again
+        let dataset = DataSet::init_from_data(text).unwrap();
+        let durations: Vec<i64> = dataset
+            .defines()
+            .iter()
+            .map(|define| define.get_event().get_duration())
+            .collect();
+        assert_eq!(durations, [minutes(26 * 60), minutes(60)]);
+    }
+}
@@ -1,4 +1,5 @@
 use super::define::SiError;
+use crate::utils::messages::Msg;
 use chrono::{DateTime, Duration, LocalResult, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use colored::Colorize;
 use serde::{Deserialize, Deserializer, Serialize};
@@ -13,12 +14,32 @@ pub struct Description {
     eventname: String,
 
     #[serde(rename = "shortDescription")]
+    #[serde(default)]
     shortdescription: String,
 
     #[serde(rename = "longDescription")]
     longdescription: String,
 }
 
+// the attribute names of the texts of a Description
+pub const TEXT_FIELDS: [&str; 3] = ["eventName", "shortDescription", "longDescription"];
+
+impl Description {
+    pub fn get_languagecode(&self) -> &str {
+        &self.languagecode
+    }
+
+    // the texts by their attribute names
+    pub fn get_texts(&self) -> [(&'static str, &str); 3] {
+        let [name, short, long] = TEXT_FIELDS;
+        [
+            (name, &self.eventname),
+            (short, &self.shortdescription),
+            (long, &self.longdescription),
+        ]
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, PartialEq)]
 pub struct SiDescriptions {
     description: Description,
@@ -51,6 +72,10 @@ impl SiStandard {
         self.sidescriptions.description.longdescription.to_string()
     }
 
+    pub fn get_description(&self) -> &Description {
+        &self.sidescriptions.description
+    }
+
     #[allow(dead_code)]
     pub fn calculate_endtime(&mut self) {
         let duration: Duration = match chrono::TimeDelta::try_milliseconds(self.duration) {
@@ -124,14 +149,14 @@ impl SiStandard {
 
         let s = if !first {
             match **display_err {
-                SiError::Gap => format!("{} #<-- Time gap", startime).red().on_custom_color(
+                SiError::Gap => format!("{} #<-- {}", startime, Msg::TimeGap).red().on_custom_color(
                     colored::CustomColor {
                         r: 10,
                         g: 50,
                         b: 50,
                     },
                 ),
-                SiError::Overlap => format!("{} #<-- Time overlap", startime)
+                SiError::Overlap => format!("{} #<-- {}", startime, Msg::TimeOverlap)
                     .red()
                     .on_custom_color(colored::CustomColor {
                         r: 10,
@@ -145,14 +170,14 @@ impl SiStandard {
         };
         let e = if first {
             match **display_err {
-                SiError::Gap => format!("{} #<-- Time gap", endtime).red().on_custom_color(
+                SiError::Gap => format!("{} #<-- {}", endtime, Msg::TimeGap).red().on_custom_color(
                     colored::CustomColor {
                         r: 10,
                         g: 50,
                         b: 50,
                     },
                 ),
-                SiError::Overlap => format!("{} #<-- Time overlap", endtime)
+                SiError::Overlap => format!("{} #<-- {}", endtime, Msg::TimeOverlap)
                     .red()
                     .on_custom_color(colored::CustomColor {
                         r: 10,
@@ -213,14 +238,16 @@ where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    let step = NaiveTime::parse_from_str("00 00:00:00.000", "00 %H:%M:%S%.3f").unwrap();
-    let naivetime = NaiveTime::parse_from_str(&s, "00 %H:%M:%S%.3f");
-    match naivetime {
-        Ok(time) => {
+    // "DD HH:MM:SS.fff", the days in front of the time of day
+    let (days, time) = s.split_once(' ').unwrap_or(("", &s));
+    let step = NaiveTime::parse_from_str("00:00:00.000", "%H:%M:%S%.3f").unwrap();
+    let naivetime = NaiveTime::parse_from_str(time, "%H:%M:%S%.3f");
+    match (days.parse::<i64>(), naivetime) {
+        (Ok(days), Ok(time)) => {
             let dur: Duration = time - step;
-            Ok(dur.num_milliseconds())
+            Ok(days * 86_400_000 + dur.num_milliseconds())
         }
-        Err(..) => Err(serde::de::Error::custom("could not calcuate the duration")),
+        _ => Err(serde::de::Error::custom("could not calcuate the duration")),
     }
 }
 
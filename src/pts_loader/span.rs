@@ -0,0 +1,117 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use chrono::{DateTime, Utc};
+
+pub type Span = (DateTime<Utc>, DateTime<Utc>);
+
+pub fn span(event: &Event) -> Option<Span> {
+    Some((event.get_starttime()?, event.get_endtime()?))
+}
+
+pub fn merge(mut spans: Vec<Span>) -> Vec<Span> {
+    spans.sort();
+    let mut merged: Vec<Span> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+// the parts of `outer` that none of the (merged) spans cover
+pub fn uncovered(outer: Span, covered: &[Span]) -> Vec<Span> {
+    let mut result = Vec::new();
+    let mut position = outer.0;
+    for &(start, end) in covered {
+        if end <= position || start >= outer.1 {
+            continue;
+        }
+        if start > position {
+            result.push((position, start));
+        }
+        position = position.max(end);
+    }
+    if position < outer.1 {
+        result.push((position, outer.1));
+    }
+    result
+}
+
+pub fn overlap(a: Span, b: Span) -> Option<Span> {
+    let start = a.0.max(b.0);
+    let end = a.1.min(b.1);
+    (start < end).then_some((start, end))
+}
+
+pub fn programmes(defines: &[Define]) -> Vec<(&Event, Span)> {
+    defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::siEvent(event) => span(event).map(|s| (event, s)),
+            _ => None,
+        })
+        .collect()
+}
+
+// the siEvent on air at `time`, the later one wins if two of them overlap
+pub fn programme_at<'a>(
+    programmes: &[(&'a Event, Span)],
+    time: DateTime<Utc>,
+) -> Option<&'a Event> {
+    programmes
+        .iter()
+        .rev()
+        .find(|(_, (from, to))| *from <= time && time < *to)
+        .map(|(event, _)| *event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::at;
+
+    fn spans(times: &[(&str, &str)]) -> Vec<Span> {
+        times
+            .iter()
+            .map(|(start, end)| (at(start), at(end)))
+            .collect()
+    }
+
+    #[test]
+    fn merge_joins_touching_and_overlapping_spans() {
+        let merged = merge(spans(&[
+            ("18:30:00", "18:40:00"),
+            ("18:00:00", "18:10:00"),
+            ("18:10:00", "18:20:00"),
+            ("18:35:00", "18:38:00"),
+        ]));
+        assert_eq!(
+            merged,
+            spans(&[("18:00:00", "18:20:00"), ("18:30:00", "18:40:00")])
+        );
+    }
+
+    #[test]
+    fn uncovered_parts() {
+        let covered = spans(&[("17:50:00", "18:10:00"), ("18:20:00", "18:30:00")]);
+        let outer = (at("18:00:00"), at("18:40:00"));
+        assert_eq!(
+            uncovered(outer, &covered),
+            spans(&[("18:10:00", "18:20:00"), ("18:30:00", "18:40:00")])
+        );
+        assert_eq!(uncovered(outer, &[]), [outer]);
+        assert!(uncovered(outer, &spans(&[("18:00:00", "18:40:00")])).is_empty());
+    }
+
+    #[test]
+    fn overlap_of_spans() {
+        let a = (at("18:00:00"), at("18:30:00"));
+        assert_eq!(
+            overlap(a, (at("18:20:00"), at("19:00:00"))),
+            Some((at("18:20:00"), at("18:30:00")))
+        );
+        assert_eq!(overlap(a, (at("18:30:00"), at("19:00:00"))), None);
+    }
+}
@@ -1,12 +1,13 @@
-use crate::pts_loader::block::Block;
+use crate::pts_loader::block::{Block, BlockError, BlockIssue};
 use crate::pts_loader::event::Event;
 use crate::utils::fluid::QueryType;
-use crate::utils::table_print;
+use crate::utils::messages::{self, Msg};
+use crate::utils::table::{Column, Table};
 use crate::utils::take::Take;
 use crate::Fluid;
 use crate::Summary;
 use crate::{commandline::commandline::Commandline, pts_loader::define::Define};
-use chrono::NaiveDate;
+use chrono::{DateTime, Datelike, Utc};
 use colored::{ColoredString, Colorize};
 
 #[derive(Clone)]
@@ -16,72 +17,120 @@ enum LengthError {
     NoError,
 }
 
-const LINE_WIDTH: usize = 256;
-
 #[derive(Clone)]
 pub struct SpecialEvent<'a> {
     vec: Vec<&'a Define>,
 }
 
 
+fn special_event_table(cmd: &Commandline) -> Table {
+    let mut table = Table::new(vec![
+        Column::new("day", Msg::ColDay.text(), 11),
+        Column::new("title", Msg::ColTitle.text(), 30),
+        Column::new("filename", Msg::ColFilename.text(), 50),
+        Column::new("programid", Msg::ColProgramId.text(), 15),
+        Column::new("start", Msg::ColStart.text(), 23),
+        Column::new("end", Msg::ColEnd.text(), 23),
+        Column::new("duration", Msg::ColDuration.text(), 12),
+        Column::new("tcin", Msg::ColTcin.text(), 12),
+        Column::new("tcout", Msg::ColTcout.text(), 12),
+        Column::new("contentid", Msg::ColContentId.text(), 20),
+        Column::new("logo", Msg::ColLogo.text(), 16),
+    ]);
+    if let Some(columns) = cmd.columns() {
+        table.select(&columns);
+    }
+    table
+}
+
 pub fn print_special_events(
     special_events: Vec<&SpecialEvent<'_>>,
-    special_event_errors: &Vec<Block<'_>>,
+    special_event_errors: &Vec<BlockError<'_>>,
     fluid_data_set: &Fluid,
     summary: &mut Summary,
     cmd: &Commandline,
 ) {
     if special_events.len() > 0 {
-        println!("Special events:");
-        table_print::print_line(LINE_WIDTH);
-        table_print::print_head();
-        table_print::print_line_cross();
+        println!("{}", Msg::SpecialEvents);
+        let mut table = special_event_table(cmd);
         special_events.iter().for_each(|special_event| {
-            if cmd.day().is_some() || cmd.today().is_some() {
-                let date: NaiveDate;
-                if cmd.day().is_some() {
-                    date = cmd.day().unwrap();
-                } else {                    
-                    date = cmd.today().unwrap();
-                }
-                   let event_date: NaiveDate = special_event.vec[0]
-                    .get_event()
-                    .get_starttime()
-                    .unwrap()
-                    .date_naive();
-                if event_date == date {
-                    let terrors = special_event.get_time_errors();
-                    let (logo_errors, length_errors) =
-                        special_event.print_table( &terrors, summary, cmd, fluid_data_set);
-                    table_print::print_line_cross();
-
-                    summary.logo_errors += logo_errors;
-                    summary.time_errors += terrors.len() as i64;
-                    summary.length_error += length_errors;
-                }
-            } else {                
-                let terrors = special_event.get_time_errors();
-                let (lerrors, length_errors) =
-                    special_event.print_table( &terrors, summary, cmd, fluid_data_set);
-                table_print::print_line_cross();
-
-                summary.logo_errors += lerrors;
-                summary.time_errors += terrors.len() as i64;
-                summary.length_error += length_errors;
-            }
+            let terrors = special_event.get_time_errors();
+            let (lerrors, length_errors) =
+                special_event.print_table(&mut table, &terrors, summary, cmd, fluid_data_set);
+            table.add_separator();
+
+            summary.logo_errors += lerrors;
+            summary.time_errors += terrors.len() as i64;
+            summary.length_error += length_errors;
         });
-        table_print::print_head();
-        table_print::print_line(LINE_WIDTH);
-    }
-    for block in special_event_errors {
-        if block.is_begin() {
-            println!("{}", "missing end to event:".red());
-            println!("{:?}", block.event());
-        } else {
-            println!("{}", "missing begin to event:".red());
-            println!("{:?}", block.event());
+        if !table.is_empty() {
+            table.print(cmd);
         }
     }
+    if special_event_errors.is_empty() {
+        return;
+    }
+    let position = |block: &Block| {
+        let event = block.event().get_event();
+        format!(
+            "#{} {} {}",
+            block.index() + 1,
+            event.starttime_to_string(cmd.utc(), cmd.fps()),
+            event.get_title()
+        )
+    };
+    let mut table = Table::new(vec![
+        Column::new("issue", Msg::ColIssue.text(), 30),
+        Column::new("block", Msg::ColBlock.text(), 50),
+        Column::new("related", Msg::ColRelated.text(), 50),
+    ]);
+    if let Some(columns) = cmd.columns() {
+        table.select(&columns);
+    }
+    for error in special_event_errors {
+        let issue = match error.issue {
+            BlockIssue::MissingEnd => Msg::MissingEnd,
+            BlockIssue::MissingBegin => Msg::MissingBegin,
+            BlockIssue::Nested => Msg::BlockNested,
+            BlockIssue::NoCommercials => Msg::BlockNoCommercials,
+            BlockIssue::CrossesSiEvent => Msg::BlockCrossesSiEvent,
+        };
+        let related = match (error.si, &error.other) {
+            (Some(si), _) => format!(
+                "{} {}",
+                si.starttime_to_string(cmd.utc(), cmd.fps()),
+                si.get_title()
+            ),
+            (None, Some(other)) => position(other),
+            (None, None) => String::new(),
+        };
+        table.add_row(vec![
+            issue.text().red(),
+            position(&error.block).normal(),
+            related.normal(),
+        ]);
+    }
+    println!("{}", Msg::BlockErrorsHeading.text().red());
+    table.print(cmd);
+}
+
+// trailers and the ignored content ids are aired without a logo
+pub fn logo_free(event: &Event, cmd: &Commandline) -> bool {
+    cmd.get_content_ids_to_ignore()
+        .iter()
+        .any(|x| event.get_contentid().contains(x))
+        || event.get_title().contains("railer")
+}
+
+// inside a block the " - 00x" and numbered titles are commercials as well,
+// outside of it " - 00x" titles are the segments of a programme
+pub fn logo_forbidden(event: &Event, cmd: &Commandline) -> bool {
+    logo_free(event, cmd)
+        || event.get_title().starts_with(" - 00")
+        || event.get_title().split(" ").collect::<Vec<&str>>()[0]
+            .to_string()
+            .parse::<i64>()
+            .is_ok()
 }
 
 impl<'a> SpecialEvent<'a> {
@@ -119,6 +168,13 @@ impl<'a> SpecialEvent<'a> {
     }
 
     pub fn get_commercials(&self) -> Vec<String> {
+        self.get_commercial_events()
+            .iter()
+            .map(|event| event.get_title())
+            .collect()
+    }
+
+    pub fn get_commercial_events(&self) -> Vec<&'a Event> {
         let mut store = Vec::new();
 
         for s in &self.vec {
@@ -127,7 +183,7 @@ impl<'a> SpecialEvent<'a> {
                     let id = event.get_contentid();
                     let title = event.get_title();
                     if id == "UHD1_WERBUNG-01" && title != " -  UHD1_WERBUNG-01" {
-                        store.push(format!("{}", title));
+                        store.push(event);
                     }
                 }
                 _ => (),
@@ -137,6 +193,14 @@ impl<'a> SpecialEvent<'a> {
         store
     }
 
+    pub fn get_starttime(&self) -> Option<DateTime<Utc>> {
+        self.vec.first()?.get_event().get_starttime()
+    }
+
+    pub fn get_span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        Some((self.get_starttime()?, self.vec.last()?.get_event().get_endtime()?))
+    }
+
     pub fn has_id_errors(&self) -> bool {
         for s in &self.vec {
             match s {
@@ -211,17 +275,7 @@ impl<'a> SpecialEvent<'a> {
         let debug_me = false;
         let logos = self.find_logo(event);
         let mut answer: String = String::new();
-        if cmd
-            .get_content_ids_to_ignore()
-            .iter()
-            .any(|x| event.get_contentid().contains(x))
-            || event.get_title().contains("railer")
-            || event.get_title().starts_with(" - 00")
-            || event.get_title().split(" ").collect::<Vec<&str>>()[0]
-                .to_string()
-                .parse::<i64>()
-                .is_ok()
-        {
+        if logo_forbidden(event, cmd) {
             if logos.len() != 0 {
                 if debug_me {
                     println!("{:?}", event);
@@ -297,17 +351,7 @@ impl<'a> SpecialEvent<'a> {
                     }
                     let contentid = event.get_contentid();
 
-                    let tcin_tcout = if cmd
-                        .get_content_ids_to_ignore()
-                        .iter()
-                        .any(|x| event.get_contentid().contains(x))
-                        || event.get_title().contains("railer")
-                        || event.get_title().starts_with(" - 00")
-                        || event.get_title().split(" ").collect::<Vec<&str>>()[0]
-                            .to_string()
-                            .parse::<i64>()
-                            .is_ok()
-                    {
+                    let tcin_tcout = if logo_forbidden(event, cmd) {
                         format!("{};{}", " ".repeat(12), " ".repeat(12))
                     } else {
                         match &event.get_tcin_tcout() {
@@ -397,17 +441,7 @@ impl<'a> SpecialEvent<'a> {
                     }
                     let contentid = event.get_contentid();
 
-                    let (tcin,tcout) = if cmd
-                        .get_content_ids_to_ignore()
-                        .iter()
-                        .any(|x| event.get_contentid().contains(x))
-                        || event.get_title().contains("railer")
-                        || event.get_title().starts_with(" - 00")
-                        || event.get_title().split(" ").collect::<Vec<&str>>()[0]
-                            .to_string()
-                            .parse::<i64>()
-                            .is_ok()
-                    {
+                    let (tcin,tcout) = if logo_forbidden(event, cmd) {
                         ("".to_string(), "".to_string())
                     } else {
                         match &event.get_tcin_tcout() {
@@ -502,7 +536,8 @@ impl<'a> SpecialEvent<'a> {
     }
 
     pub fn print_table(
-        &self,        
+        &self,
+        table: &mut Table,
         time_errors: &Vec<String>,
         summary: &mut Summary,
         cmd: &Commandline,
@@ -685,12 +720,7 @@ impl<'a> SpecialEvent<'a> {
                         .take(23)
                         .red()
                         .clear();
-                    let (mut tcin, mut tcout) = if cmd
-                        .get_content_ids_to_ignore()
-                        .iter()
-                        .any(|x| event.get_contentid().contains(x))
-                        || event.get_title().contains("railer")
-                    {
+                    let (mut tcin, mut tcout) = if logo_free(event, cmd) {
                         (
                             format!("{}", " ".repeat(12)).red().clear(),
                             format!("{}", " ".repeat(12)).red().clear(),
@@ -728,7 +758,7 @@ impl<'a> SpecialEvent<'a> {
                         tcin = tcin.white().on_bright_red();
                         tcout = tcout.white().on_bright_red();
                         contentid_string = contentid_string.white().bright_red();
-                        logostr_string = "ALTE TAFEL".to_string().take(16).white().bright_red();
+                        logostr_string = Msg::AlteTafel.text().white().bright_red();
                         summary.alte_tafel_error += 1;  
                     } else 
                     if contentid.contains("-") && contentid.len() == "1529458-0".len() {
@@ -870,12 +900,11 @@ impl<'a> SpecialEvent<'a> {
                     }
 
                     if cmd.verbose() {
-                        println!(
-                            "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |",
+                        table.add_row(vec![
                             if index == 0 {
-                                event.get_starttime().map(|d| d.format("%A").to_string()).unwrap_or_default().take(11).yellow()
+                                event.get_starttime().map(|d| messages::weekday(d.weekday())).unwrap_or_default().yellow()
                             } else {
-                                "".to_string().take(11).red().clear()
+                                "".clear()
                             },
                             title_string,
                             content_string,
@@ -887,13 +916,10 @@ impl<'a> SpecialEvent<'a> {
                             tcout,
                             contentid_string,
                             logostr_string,
-                        );
+                        ]);
 
                         for logo in &logos {
-                            let mut logostr = logo.get_event().get_logo();
-                            if logostr.len() > 14 {
-                                logostr = logostr.drain(0..14).collect::<String>();
-                            }
+                            let logostr = logo.get_event().get_logo();
                             let duration = logo.get_event().duration_to_string(cmd.fps());
 
                             let is_logo_not_layout = match logo {
@@ -929,41 +955,26 @@ impl<'a> SpecialEvent<'a> {
                                 logoerrors += 1;
                             }
                             
-                                println!(
-                                    "| {:11} | {:30} | {:50} | {:15} | {:23} | {:23} | {:12} | {} | {:20} | {} |",
-                                    " ",
-                                    " ",
-                                    " ",
-                                    c_color(logo.get_event().programid_to_string()),
-                                    c_color(logo.get_event()
-                                        .starttime_to_string(cmd.utc(), cmd.fps())),                                        
-                                    c_color(logo.get_event()
-                                        .endtime_to_string(cmd.utc(), cmd.fps())),
-                                    c_color(duration.to_string()),
-                                    format!("{} | {}", " ".repeat(12), " ".repeat(12)),
-                                    c_color(logo.get_event().get_contentid()),
-                                    c_color(logostr.take(16))
-                                );
-
-                            
+                            table.add_row(vec![
+                                "".clear(),
+                                "".clear(),
+                                "".clear(),
+                                c_color(logo.get_event().programid_to_string()),
+                                c_color(logo.get_event()
+                                    .starttime_to_string(cmd.utc(), cmd.fps())),
+                                c_color(logo.get_event()
+                                    .endtime_to_string(cmd.utc(), cmd.fps())),
+                                c_color(duration.to_string()),
+                                "".clear(),
+                                "".clear(),
+                                c_color(logo.get_event().get_contentid()),
+                                c_color(logostr),
+                            ]);
                         }
-                    
+
                         if logoerrors != logo_errors_found {
                             logo_errors_found = logoerrors;
-                            println!(
-                                "| {:11} | {:30} | {:50} | {:15} | {:23} | {:23} | {:12} | {:12} | {:12} | {:20} | {} |",
-                                "-".repeat(11).black().on_red(),
-                                "-".repeat(30).black().on_red(),
-                                "-".repeat(50).black().on_red(),
-                                "-".repeat(15).black().on_red(),
-                                "-".repeat(23).black().on_red(),
-                                "-".repeat(23).black().on_red(),
-                                "-".repeat(12).black().on_red(),
-                                "-".repeat(12).black().on_red(),
-                                "-".repeat(12).black().on_red(),
-                                "-".repeat(20).black().on_red(),
-                                "Missing logo".to_string().take(16).black().on_red(),
-                            );
+                            table.add_marker(Msg::MissingLogo.text());
                         }
 
                     }
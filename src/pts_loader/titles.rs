@@ -0,0 +1,228 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::pts_loader::interruptions::programme_id;
+use crate::pts_loader::span::{overlap, programmes, span, Span};
+use crate::pts_loader::special_event::SpecialEvent;
+use crate::utils::ball_rules::{BallRules, Kind};
+use crate::utils::sendepause_rules::SendepauseRules;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TitleIssue {
+    // the siEvent title matches none of the vaEvents it covers
+    EpgMismatch,
+    // no siEvent with a matching title covers the programme
+    NotInEpg,
+}
+
+pub struct TitleError<'a> {
+    pub issue: TitleIssue,
+    pub si: Option<&'a Event>,
+    // the best matching vaEvent, the first segment for NotInEpg
+    pub va: Option<&'a Event>,
+    pub similarity: f64,
+}
+
+// " - 001 Film: Teil 1" is "film teil 1"
+fn words(title: &str) -> Vec<String> {
+    let title = title.trim_start();
+    let title = match title.strip_prefix('-') {
+        Some(rest) => rest
+            .trim_start()
+            .trim_start_matches(|c: char| c.is_ascii_digit()),
+        None => title,
+    };
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// share of the words of the shorter title that are in the longer one
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (words(a), words(b));
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    if short.is_empty() {
+        return 0.0;
+    }
+    let found = short.iter().filter(|word| long.contains(word)).count();
+    found as f64 / short.len() as f64
+}
+
+// Pairs every siEvent with the vaEvents in its time window and those of the
+// same programId root. Breaks and Sendepausen are not compared, and only
+// programme segments need an siEvent, not trailers, balls or fillers.
+pub fn check<'a>(
+    defines: &'a [Define],
+    special_events: &[SpecialEvent<'a>],
+    pauses: &SendepauseRules,
+    balls: &BallRules,
+    threshold: f64,
+) -> Vec<TitleError<'a>> {
+    let breaks: Vec<Span> = special_events
+        .iter()
+        .filter_map(|special_event| special_event.get_span())
+        .collect();
+    let sievents: Vec<(&Event, Span)> = programmes(defines)
+        .into_iter()
+        .filter(|(event, _)| !pauses.is_sendepause(event))
+        .collect();
+    let vaevents: Vec<(&Event, Span)> = defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::vaEvent(event) => span(event).map(|s| (event, s)),
+            _ => None,
+        })
+        .filter(|(event, s)| {
+            !pauses.is_loop(event) && !breaks.iter().any(|b| overlap(*b, *s).is_some())
+        })
+        .collect();
+    let pairs = |si: &Event, s: Span, va: &Event, v: Span| {
+        let id = programme_id(si);
+        overlap(s, v).is_some() || (!id.is_empty() && id == programme_id(va))
+    };
+
+    let mut errors = Vec::new();
+    for (si, s) in &sievents {
+        let best = vaevents
+            .iter()
+            .filter(|(va, v)| pairs(si, *s, va, *v))
+            .map(|(va, _)| (*va, similarity(&si.get_title(), &va.get_title())))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((va, value)) = best.filter(|(_, value)| *value < threshold) {
+            errors.push(TitleError {
+                issue: TitleIssue::EpgMismatch,
+                si: Some(si),
+                va: Some(va),
+                similarity: value,
+            });
+        }
+    }
+
+    let mut groups: Vec<(String, Vec<(&Event, Span)>)> = Vec::new();
+    for (va, v) in vaevents.iter().filter(|(va, _)| balls.kind(va) == Kind::Segment) {
+        let id = programme_id(va);
+        match groups.iter_mut().find(|(p, _)| *p == id) {
+            Some((_, segments)) => segments.push((va, *v)),
+            None => groups.push((id, vec![(va, *v)])),
+        }
+    }
+    for (_, segments) in groups {
+        let first = segments[0].0;
+        let best = sievents
+            .iter()
+            .filter(|(si, s)| segments.iter().any(|(va, v)| pairs(si, *s, va, *v)))
+            .map(|(si, _)| (*si, similarity(&si.get_title(), &first.get_title())))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match best {
+            Some((_, value)) if value >= threshold => (),
+            _ => errors.push(TitleError {
+                issue: TitleIssue::NotInEpg,
+                si: best.map(|(si, _)| si),
+                va: Some(first),
+                similarity: best.map(|(_, value)| value).unwrap_or_default(),
+            }),
+        }
+    }
+    errors.sort_by_key(|error| match error.issue {
+        TitleIssue::EpgMismatch => error.si.and_then(|si| si.get_starttime()),
+        TitleIssue::NotInEpg => error.va.and_then(|va| va.get_starttime()),
+    });
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::block;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+    use crate::utils::block_rules::BlockRules;
+
+    #[test]
+    fn words_of_titles() {
+        assert_eq!(words(" - 001 Film: Teil 1"), ["film", "teil", "1"]);
+        assert_eq!(words("Tatort - Der Fall"), ["tatort", "der", "fall"]);
+        assert!(words(" - 002 ").is_empty());
+    }
+
+    #[test]
+    fn similarity_of_titles() {
+        assert_eq!(similarity("Tatort", " - 001 TATORT: Der Fall"), 1.0);
+        assert_eq!(similarity("Der große Fall", "Der kleine Fall"), 2.0 / 3.0);
+        assert_eq!(similarity("Doku", "Film"), 0.0);
+        assert_eq!(similarity("", "Film"), 0.0);
+    }
+
+    fn issues(pts: &Pts) -> Vec<(TitleIssue, String, String)> {
+        let defines = pts.defines();
+        let (special_events, _) = block::parse(&defines, &BlockRules::default_rules());
+        let pauses = SendepauseRules::default_rules();
+        check(&defines, &special_events, &pauses, &BallRules::default_rules(), 0.5)
+            .iter()
+            .map(|error| {
+                let si = error.si.map(|si| si.get_title()).unwrap_or_default();
+                let va = error.va.map(|va| va.get_title()).unwrap_or_default();
+                (error.issue, si, va)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn segments_breaks_and_pauses_match_their_programme() {
+        let mut pts = Pts::new();
+        pts.si("Der Film", "18:00:00", minutes(45), "P100")
+            .film(1, "18:00:00", minutes(20))
+            .va(
+                "Dranbleiben",
+                "3ecea6b8084f35c2634d",
+                "18:20:00",
+                10_000,
+                "D1",
+            )
+            .va("Spot", "UHD1_WERBUNG-01", "18:20:10", minutes(4), "W1")
+            .va(
+                "Bald weiter",
+                "02e9d82071c7735de5cd",
+                "18:24:10",
+                50_000,
+                "E1",
+            )
+            .film(2, "18:25:00", minutes(20))
+            .si("Sendepause", "18:45:00", minutes(60), "S1")
+            .va(
+                "Nachtschleife",
+                "7000000-000001",
+                "18:45:00",
+                minutes(60),
+                "L1",
+            );
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn trailers_balls_and_fillers_need_no_epg() {
+        let mut pts = Pts::new();
+        pts.si("Der Film", "18:00:00", minutes(45), "P100")
+            .film(1, "18:00:00", minutes(45))
+            .va("Trailer: Tatort", "7000000-000002", "18:45:00", 20_000, "T1")
+            .va("Ball", "5675d8c63df2424bf286", "18:45:20", 5_000, "B1")
+            .va("Hinweis", "7000000-000003", "18:45:25", 60_000, "H1");
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn titles_that_do_not_match() {
+        let mut pts = Pts::new();
+        pts.si("Doku: Die Alpen", "18:00:00", minutes(45), "P100")
+            .va("Tatort", "1572515-971182", "18:00:00", minutes(45), "P200");
+        let (si, va) = ("Doku: Die Alpen".to_string(), "Tatort".to_string());
+        assert_eq!(
+            issues(&pts),
+            [
+                (TitleIssue::EpgMismatch, si.clone(), va.clone()),
+                (TitleIssue::NotInEpg, si, va),
+            ]
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::utils::ball_rules::{BallRules, Kind};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BallIssue {
+    // the event before the ball is not one of the expected kinds
+    Before,
+    After,
+}
+
+pub struct BallError<'a> {
+    pub issue: BallIssue,
+    pub ball: &'a Event,
+    pub neighbour: &'a Event,
+    pub kind: Kind,
+}
+
+// Compares the vaEvents right before and after every ball with the rules.
+// A ball at the start or the end of the file has nothing to compare with.
+pub fn check<'a>(defines: &'a [Define], rules: &BallRules) -> Vec<BallError<'a>> {
+    let mut events: Vec<&Event> = defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::vaEvent(event) => Some(event),
+            _ => None,
+        })
+        .collect();
+    events.sort_by_key(|event| event.get_starttime());
+
+    let mut errors = Vec::new();
+    for (i, ball) in events.iter().enumerate() {
+        if rules.kind(ball) != Kind::Ball {
+            continue;
+        }
+        let neighbours = [
+            (BallIssue::Before, i.checked_sub(1), &rules.before),
+            (BallIssue::After, Some(i + 1), &rules.after),
+        ];
+        for (issue, j, expected) in neighbours {
+            let Some(neighbour) = j.and_then(|j| events.get(j)) else {
+                continue;
+            };
+            let kind = rules.kind(neighbour);
+            if !expected.is_empty() && !expected.contains(&kind) {
+                errors.push(BallError {
+                    issue,
+                    ball,
+                    neighbour,
+                    kind,
+                });
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    fn issues(pts: &Pts) -> Vec<(BallIssue, String, Kind)> {
+        let defines = pts.defines();
+        check(&defines, &BallRules::default_rules())
+            .iter()
+            .map(|error| (error.issue, error.neighbour.get_title(), error.kind))
+            .collect()
+    }
+
+    #[test]
+    fn ball_between_trailer_and_segment() {
+        let mut pts = Pts::new();
+        pts.va("Trailer Serie", "6000000-000001", "18:00:00", 20_000, "T1")
+            .va("Ball", "5675d8c63df2424bf286", "18:00:20", 5_000, "B1")
+            .film(1, "18:00:25", minutes(20));
+        assert!(issues(&pts).is_empty());
+    }
+
+    #[test]
+    fn ball_in_the_wrong_place() {
+        let mut pts = Pts::new();
+        pts.film(1, "18:00:00", minutes(20))
+            .va("Ball", "5675d8c63df2424bf286", "18:20:00", 5_000, "B1")
+            .va("Spot", "UHD1_WERBUNG-01", "18:20:05", 20_000, "W1")
+            .va("Ball", "5675d8c63df2424bf286", "18:20:25", 5_000, "B2");
+        assert_eq!(
+            issues(&pts),
+            [
+                (BallIssue::Before, " - 001 Film".to_string(), Kind::Segment),
+                (BallIssue::After, "Spot".to_string(), Kind::Commercial),
+                (BallIssue::Before, "Spot".to_string(), Kind::Commercial),
+            ]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::utils::fluid::Fluid;
+use crate::utils::format_rules::{allowed, FormatRules};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FormatIssue {
+    Resolution,
+    Format,
+}
+
+pub struct FormatError<'a> {
+    pub issue: FormatIssue,
+    pub event: &'a Event,
+    // the fluid value and the values allowed on the service
+    pub value: String,
+    pub allowed: Vec<String>,
+    pub filename: String,
+}
+
+// Material that is not in fluid or has no value is not checked.
+pub fn check<'a>(
+    defines: &'a [Define],
+    rules: &FormatRules,
+    fluid: &Fluid,
+) -> Vec<FormatError<'a>> {
+    let mut errors = Vec::new();
+    for define in defines {
+        let Define::vaEvent(event) = define else {
+            continue;
+        };
+        let Some(service) = rules.service(&event.get_serviceid()) else {
+            continue;
+        };
+        let Some(entry) = fluid.entry(event) else {
+            continue;
+        };
+        let filename = entry.get("Filename").cloned().unwrap_or_default();
+        let checks = [
+            (FormatIssue::Resolution, "Res.", &service.resolutions),
+            (FormatIssue::Format, "Format", &service.formats),
+        ];
+        for (issue, column, values) in checks {
+            let Some(value) = entry.get(column).filter(|value| !value.trim().is_empty()) else {
+                continue;
+            };
+            if !allowed(value, values) {
+                errors.push(FormatError {
+                    issue,
+                    event,
+                    value: value.clone(),
+                    allowed: values.clone(),
+                    filename: filename.clone(),
+                });
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    fn schedule() -> Pts {
+        let mut pts = Pts::new();
+        pts.va("Film", "1572515-971182", "18:00:00", minutes(20), "P100")
+            .va("Füller", "5000000-000001", "18:20:00", minutes(5), "F1")
+            .va("Serie", "1600000-100001", "18:25:00", minutes(20), "P200")
+            .va("Neu", "1800000-100001", "18:45:00", minutes(20), "P300");
+        pts
+    }
+
+    fn fluid() -> Fluid {
+        Fluid::from_entries(&[
+            &[
+                ("ContentId", "1572515-971182"),
+                ("Res.", " uhd "),
+                ("Format", "HDR"),
+                ("Filename", "f1.mxf"),
+            ],
+            &[
+                ("ContentId", "5000000-000001"),
+                ("Res.", "HD"),
+                ("Format", "SDR"),
+                ("Filename", "f2.mxf"),
+            ],
+            // not known yet
+            &[
+                ("ContentId", "1600000-100001"),
+                ("Res.", ""),
+                ("Format", ""),
+            ],
+        ])
+    }
+
+    fn issues(rules: &FormatRules) -> Vec<(String, FormatIssue, String, String)> {
+        let defines = schedule().defines();
+        check(&defines, rules, &fluid())
+            .iter()
+            .map(|error| {
+                let title = error.event.get_title();
+                (
+                    title,
+                    error.issue,
+                    error.value.clone(),
+                    error.filename.clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn hd_material_on_the_uhd_service() {
+        assert_eq!(
+            issues(&FormatRules::default_rules()),
+            [(
+                "Füller".to_string(),
+                FormatIssue::Resolution,
+                "HD".to_string(),
+                "f2.mxf".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn formats_and_other_services() {
+        let rules: FormatRules =
+            serde_json::from_str(r#"{"services": [{"service_id": "UHD1", "formats": ["hdr"]}]}"#)
+                .unwrap();
+        let expected = ("Füller".to_string(), FormatIssue::Format, "SDR".to_string());
+        let found: Vec<_> = issues(&rules)
+            .into_iter()
+            .map(|(title, issue, value, _)| (title, issue, value))
+            .collect();
+        assert_eq!(found, [expected]);
+
+        let rules: FormatRules =
+            serde_json::from_str(r#"{"services": [{"service_id": "UHD2", "formats": ["HDR"]}]}"#)
+                .unwrap();
+        assert!(issues(&rules).is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+use crate::pts_loader::dataset::local_time;
+use crate::pts_loader::define::Define;
+use crate::pts_loader::event::Event;
+use crate::utils::messages::Msg;
+use crate::utils::youth_rules::{AirtimeWindow, YouthRules};
+use crate::Fluid;
+use chrono::{Duration, NaiveDateTime};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum YouthIssue {
+    // starts outside of the window
+    TooEarly,
+    // starts inside of the window but runs past its end
+    RunsOutside,
+    // a trailer for rated material outside of the window
+    Trailer,
+}
+
+impl YouthIssue {
+    pub fn msg(self) -> Msg {
+        match self {
+            YouthIssue::TooEarly => Msg::YouthTooEarly,
+            YouthIssue::RunsOutside => Msg::YouthRunsOutside,
+            YouthIssue::Trailer => Msg::YouthTrailer,
+        }
+    }
+}
+
+pub struct YouthError<'a> {
+    pub issue: YouthIssue,
+    pub event: &'a Event,
+    pub age: u32,
+    pub window: &'a AirtimeWindow,
+    // local time, the law knows no utc
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+// the end of the window `time` lies in, None if it lies outside
+fn window_end(time: NaiveDateTime, window: &AirtimeWindow) -> Option<NaiveDateTime> {
+    let t = time.time();
+    let date = time.date();
+    if window.from <= window.until {
+        return (window.from <= t && t < window.until).then(|| date.and_time(window.until));
+    }
+    if t >= window.from {
+        Some((date + Duration::days(1)).and_time(window.until))
+    } else if t < window.until {
+        Some(date.and_time(window.until))
+    } else {
+        None
+    }
+}
+
+// " - 001 Film" is part of "Film"
+fn programme_title(title: &str) -> &str {
+    let title = title.trim_start();
+    match title.strip_prefix('-') {
+        Some(rest) => rest
+            .trim_start()
+            .trim_start_matches(|c: char| c.is_ascii_digit())
+            .trim(),
+        None => title.trim(),
+    }
+}
+
+fn is_trailer(event: &Event) -> bool {
+    event.get_title().contains("railer")
+}
+
+// Rated material has to start and end within the airtime window of its
+// rating. Trailers without a rating of their own take the rating of the
+// programme their title names.
+pub fn check<'a>(
+    defines: &'a [Define],
+    rules: &'a YouthRules,
+    fluid: &Fluid,
+) -> Vec<YouthError<'a>> {
+    let events: Vec<(&Event, Option<u32>)> = defines
+        .iter()
+        .filter_map(|define| match define {
+            Define::vaEvent(event) => Some((event, rules.rating(event, fluid))),
+            _ => None,
+        })
+        .collect();
+    let rated: Vec<(String, u32)> = events
+        .iter()
+        .filter(|(event, _)| !is_trailer(event))
+        .filter_map(|(event, age)| Some((programme_title(&event.get_title()).to_string(), (*age)?)))
+        .filter(|(title, _)| title.chars().count() >= 3)
+        .collect();
+
+    let mut errors = Vec::new();
+    for (event, age) in events {
+        let trailer = is_trailer(event);
+        let age = match age {
+            Some(age) => Some(age),
+            None if trailer => {
+                let title = event.get_title();
+                rated
+                    .iter()
+                    .filter(|(programme, _)| title.contains(programme.as_str()))
+                    .map(|(_, age)| *age)
+                    .max()
+            }
+            None => None,
+        };
+        let Some(age) = age else {
+            continue;
+        };
+        let Some(window) = rules.window(age) else {
+            continue;
+        };
+        let (Some(start), Some(end)) = (event.get_starttime(), event.get_endtime()) else {
+            continue;
+        };
+        let (start, end) = (local_time(start, false), local_time(end, false));
+        let issue = match window_end(start, window) {
+            None => YouthIssue::TooEarly,
+            Some(until) if end > until => YouthIssue::RunsOutside,
+            Some(_) => continue,
+        };
+        errors.push(YouthError {
+            issue: if trailer { YouthIssue::Trailer } else { issue },
+            event,
+            age,
+            window,
+            start,
+            end,
+        });
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{at, local, minutes, Pts};
+
+    fn window(from: &str, until: &str) -> AirtimeWindow {
+        AirtimeWindow {
+            age: 16,
+            from: from.parse().unwrap(),
+            until: until.parse().unwrap(),
+        }
+    }
+
+    fn time(time: &str) -> NaiveDateTime {
+        at(time).naive_utc()
+    }
+
+    #[test]
+    fn windows_over_midnight() {
+        let night = window("22:00:00", "06:00:00");
+        assert_eq!(window_end(time("21:59:59"), &night), None);
+        let end = Some(time("2024-05-02T06:00:00"));
+        assert_eq!(window_end(time("22:00:00"), &night), end);
+        assert_eq!(window_end(time("05:00:00"), &night), Some(time("06:00:00")));
+        assert_eq!(window_end(time("06:00:00"), &night), None);
+
+        let evening = window("20:00:00", "23:00:00");
+        assert_eq!(
+            window_end(time("21:00:00"), &evening),
+            Some(time("23:00:00"))
+        );
+        assert_eq!(window_end(time("23:30:00"), &evening), None);
+    }
+
+    #[test]
+    fn programme_titles_of_segments() {
+        assert_eq!(programme_title(" - 001 Film"), "Film");
+        assert_eq!(programme_title("-12 Film "), "Film");
+        assert_eq!(programme_title("Film - 001"), "Film - 001");
+    }
+
+    #[test]
+    fn rated_material_and_trailers_outside_of_their_window() {
+        let mut pts = Pts::new();
+        pts.va(
+            "Trailer Film heute",
+            "5a3e0c3c1c8e4f3d9a77",
+            &local("20:00:00"),
+            30_000,
+            "T1",
+        )
+        .va(
+            "Film",
+            "1572515-971182",
+            &local("21:30:00"),
+            minutes(20),
+            "P100",
+        )
+        .va(
+            " - 001 Nachtfilm",
+            "1600000-100001",
+            &local("22:00:00"),
+            minutes(60),
+            "P200",
+        )
+        .va(
+            "Frühfilm",
+            "1700000-100001",
+            &local("05:30:00"),
+            minutes(60),
+            "P300",
+        );
+        let defines = pts.defines();
+        let fluid = Fluid::from_entries(&[
+            &[("ContentId", "1572515"), ("FSK", "16")],
+            &[("ContentId", "1600000"), ("FSK", "16")],
+            &[("ContentId", "1700000"), ("FSK", "FSK 18")],
+        ]);
+        let rules = YouthRules::default_rules();
+        let issues: Vec<(String, YouthIssue, u32)> = check(&defines, &rules, &fluid)
+            .iter()
+            .map(|error| (error.event.get_title(), error.issue, error.age))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                ("Trailer Film heute".to_string(), YouthIssue::Trailer, 16),
+                ("Film".to_string(), YouthIssue::TooEarly, 16),
+                ("Frühfilm".to_string(), YouthIssue::RunsOutside, 18),
+            ]
+        );
+    }
+}
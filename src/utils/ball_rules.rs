@@ -0,0 +1,125 @@
+use crate::pts_loader::event::Event;
+use crate::pts_loader::interruptions::is_segment;
+use serde::Deserialize;
+use std::fmt;
+
+pub const DEFAULT_RULES: &str = include_str!("ball_rules.json");
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    Ball,
+    Trailer,
+    Segment,
+    Commercial,
+    Other,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Kind::Ball => "ball",
+            Kind::Trailer => "trailer",
+            Kind::Segment => "segment",
+            Kind::Commercial => "commercial",
+            Kind::Other => "other",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+// Where the station ident ("ball") has to be: what may air right before and
+// right after it. An empty list allows everything.
+#[derive(Deserialize)]
+pub struct BallRules {
+    pub content_ids: Vec<String>,
+    // parts of trailer titles, other trailers are recognized by their length
+    #[serde(default)]
+    pub trailer_titles: Vec<String>,
+    // seconds
+    pub trailer_min: i64,
+    pub trailer_max: i64,
+    pub segment_min: i64,
+    #[serde(default)]
+    pub before: Vec<Kind>,
+    #[serde(default)]
+    pub after: Vec<Kind>,
+}
+
+impl BallRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("ball_rules.json is valid")
+    }
+
+    pub fn kind(&self, event: &Event) -> Kind {
+        let id = event.get_contentid();
+        let title = event.get_title();
+        let duration = event.get_duration();
+        if self
+            .content_ids
+            .iter()
+            .any(|x| !x.is_empty() && id.contains(x.as_str()))
+        {
+            Kind::Ball
+        } else if id.contains("WERBUNG") {
+            Kind::Commercial
+        } else if self
+            .trailer_titles
+            .iter()
+            .any(|x| !x.is_empty() && title.contains(x.as_str()))
+        {
+            Kind::Trailer
+        } else if is_segment(event) {
+            Kind::Segment
+        } else if self.trailer_min * 1000 <= duration && duration <= self.trailer_max * 1000 {
+            Kind::Trailer
+        } else if self.segment_min * 1000 <= duration {
+            Kind::Segment
+        } else {
+            Kind::Other
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pts_loader::fixtures::{minutes, Pts};
+
+    #[test]
+    fn kinds_of_material() {
+        let mut pts = Pts::new();
+        pts.va("Ball", "5675d8c63df2424bf286", "18:00:00", 5_000, "B1")
+            .va("Spot", "UHD1_WERBUNG-01", "18:00:05", 20_000, "W1")
+            .va(
+                "Trailer Serie",
+                "6000000-000001",
+                "18:00:25",
+                minutes(2),
+                "T1",
+            )
+            .film(1, "18:02:25", 20_000)
+            .va("Vorschau", "6000000-000002", "18:02:45", 30_000, "T2")
+            .va("Doku", "1700000-100001", "18:03:15", minutes(3), "P300")
+            .va("Jingle", "6000000-000003", "18:06:15", 5_000, "J1");
+        let rules = BallRules::default_rules();
+        let kinds: Vec<Kind> = pts
+            .defines()
+            .iter()
+            .map(|define| rules.kind(define.get_event()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                Kind::Ball,
+                Kind::Commercial,
+                Kind::Trailer,
+                Kind::Segment,
+                Kind::Trailer,
+                Kind::Segment,
+                Kind::Other,
+            ]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::utils::rules::load_rules;
+use serde::Deserialize;
+
+pub const DEFAULT_RULES: &str = include_str!("block_rules.json");
+
+// The content ids that open and close a special event block.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct BlockRules {
+    pub begin: Vec<String>,
+    pub end: Vec<String>,
+}
+
+impl Default for BlockRules {
+    fn default() -> Self {
+        BlockRules::default_rules()
+    }
+}
+
+impl BlockRules {
+    pub fn default_rules() -> Self {
+        load_rules(None, DEFAULT_RULES).expect("block_rules.json is valid")
+    }
+
+    pub fn is_begin(&self, content_id: &str) -> bool {
+        self.begin.iter().any(|x| x == content_id)
+    }
+
+    pub fn is_end(&self, content_id: &str) -> bool {
+        self.end.iter().any(|x| x == content_id)
+    }
+}
@@ -0,0 +1,61 @@
+use crate::pts_loader::event::Event;
+use crate::utils::fluid::{Fluid, QueryType};
+use serde::Deserialize;
+
+pub const DEFAULT_RULES: &str = include_str!("compliance_rules.json");
+
+#[derive(Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Coverage {
+    // the layout has to be on air for the whole duration
+    Whole,
+    // at the start, after every break and at the end of the programme
+    Points,
+}
+
+#[derive(Deserialize)]
+pub struct ComplianceRule {
+    pub name: String,
+    pub layout: String,
+    pub coverage: Coverage,
+    #[serde(default)]
+    pub content_ids: Vec<String>,
+    // fluid `Class` values, needs --fluid
+    #[serde(default)]
+    pub classes: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ComplianceRules {
+    pub rules: Vec<ComplianceRule>,
+}
+
+impl ComplianceRule {
+    pub fn affects(&self, event: &Event, fluid: &Fluid) -> bool {
+        let id = event.get_contentid();
+        if self
+            .content_ids
+            .iter()
+            .any(|x| !x.is_empty() && id.contains(x))
+        {
+            return true;
+        }
+        if self.classes.is_empty() {
+            return false;
+        }
+        match fluid.query(event, QueryType::Class) {
+            Some(class) => self
+                .classes
+                .iter()
+                .any(|x| x.trim().eq_ignore_ascii_case(class.trim())),
+            None => false,
+        }
+    }
+}
+
+impl ComplianceRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("compliance_rules.json is valid")
+    }
+}
@@ -0,0 +1,219 @@
+use crate::utils::messages::Msg;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use std::io::{Error, ErrorKind};
+
+pub struct Decoded {
+    pub text: String,
+    pub encoding: &'static Encoding,
+    pub bom: bool,
+}
+
+// accepts the whatwg labels plus the spellings used by `--encoding`
+pub fn lookup(label: &str) -> Option<&'static Encoding> {
+    let label = label.trim().to_lowercase();
+    match label.as_str() {
+        "windows1252" | "win" | "cp1252" | "ansi" => Some(WINDOWS_1252),
+        "utf8" | "linux" => Some(UTF_8),
+        "utf16" | "utf16le" => Some(UTF_16LE),
+        "utf16be" => Some(UTF_16BE),
+        _ => Encoding::for_label(label.as_bytes()),
+    }
+}
+
+// the encoding attribute of `<?xml version="1.0" encoding="..."?>`
+fn declared_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(1024)];
+    if !head.starts_with(b"<?xml") {
+        return None;
+    }
+    let end = head.windows(2).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&head[..end]).ok()?;
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = rest[1..].split(quote).next()?;
+    lookup(value)
+}
+
+// a forced encoding wins, then a byte order mark, utf-16 without bom,
+// the xml declaration and finally utf-8 with windows-1252 as fallback
+pub fn decode(bytes: &[u8], forced: Option<&str>) -> Result<Decoded, Error> {
+    let (encoding, bom_length, sniffed) = match forced {
+        Some(label) => match lookup(label) {
+            Some(encoding) => {
+                let bom_length = match Encoding::for_bom(bytes) {
+                    Some((bom, length)) if bom == encoding => length,
+                    _ => 0,
+                };
+                (encoding, bom_length, false)
+            }
+            None => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    Msg::UnknownEncoding.fill(&[&format!("{:?}", label)]),
+                ))
+            }
+        },
+        None => match Encoding::for_bom(bytes) {
+            Some((encoding, length)) => (encoding, length, false),
+            None if bytes.starts_with(b"<\0") => (UTF_16LE, 0, false),
+            None if bytes.starts_with(b"\0<") => (UTF_16BE, 0, false),
+            None => (declared_encoding(bytes).unwrap_or(UTF_8), 0, true),
+        },
+    };
+
+    let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_length..]);
+    if had_errors && sniffed && encoding == UTF_8 {
+        // files saved on windows often claim utf-8 but are not
+        let (text, _) = WINDOWS_1252.decode_without_bom_handling(bytes);
+        return Ok(Decoded {
+            text: text.into_owned(),
+            encoding: WINDOWS_1252,
+            bom: false,
+        });
+    }
+    Ok(Decoded {
+        text: text.into_owned(),
+        encoding,
+        bom: bom_length > 0,
+    })
+}
+
+impl Decoded {
+    // encodes text in the same encoding (and with the same bom) it was read with
+    pub fn encode(&self, text: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        if self.encoding == UTF_16LE || self.encoding == UTF_16BE {
+            let big_endian = self.encoding == UTF_16BE;
+            let units = self
+                .bom
+                .then_some('\u{feff}')
+                .into_iter()
+                .chain(text.chars());
+            for unit in units.collect::<String>().encode_utf16() {
+                if big_endian {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                } else {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                }
+            }
+            return bytes;
+        }
+        if self.bom {
+            bytes.extend_from_slice(b"\xEF\xBB\xBF");
+        }
+        let (encoded, _, _) = self.encoding.encode(text);
+        bytes.extend_from_slice(&encoded);
+        bytes
+    }
+}
+
+// the text is utf-8 once it is decoded, a declaration saying otherwise
+// would make the xml parser decode it a second time
+pub fn declare_utf8(text: &str) -> String {
+    if !text.starts_with("<?xml") {
+        return text.to_string();
+    }
+    let Some(end) = text.find("?>") else {
+        return text.to_string();
+    };
+    let declaration = &text[..end];
+    let Some(start) = declaration.find("encoding") else {
+        return text.to_string();
+    };
+    let rest = &declaration[start..];
+    let value = rest.find(['"', '\'']).and_then(|open| {
+        let quote = rest[open..].chars().next()?;
+        let close = rest[open + 1..].find(quote)?;
+        Some((start + open + 1, start + open + 1 + close))
+    });
+    match value {
+        Some((from, to)) => format!("{}UTF-8{}", &text[..from], &text[to..]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?><PTS title=\"Été\"/>";
+
+    fn utf16(text: &str, bom: bool, big_endian: bool) -> Vec<u8> {
+        let units = bom.then_some('\u{feff}').into_iter().chain(text.chars());
+        units
+            .collect::<String>()
+            .encode_utf16()
+            .flat_map(|unit| match big_endian {
+                true => unit.to_be_bytes(),
+                false => unit.to_le_bytes(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(lookup("ANSI"), Some(WINDOWS_1252));
+        assert_eq!(lookup("latin1"), Some(WINDOWS_1252));
+        assert_eq!(lookup("utf16be"), Some(UTF_16BE));
+        assert_eq!(lookup("klingon"), None);
+    }
+
+    #[test]
+    fn utf8_with_and_without_bom() {
+        let decoded = decode(XML.as_bytes(), None).unwrap();
+        assert_eq!(
+            (decoded.text.as_str(), decoded.encoding, decoded.bom),
+            (XML, UTF_8, false)
+        );
+        let bytes = [b"\xEF\xBB\xBF".as_slice(), XML.as_bytes()].concat();
+        let decoded = decode(&bytes, None).unwrap();
+        assert_eq!((decoded.text.as_str(), decoded.bom), (XML, true));
+        assert_eq!(decoded.encode(&decoded.text), bytes);
+    }
+
+    #[test]
+    fn utf16_by_bom_and_by_the_first_character() {
+        for (bom, big_endian) in [(true, false), (true, true), (false, false), (false, true)] {
+            let bytes = utf16(XML, bom, big_endian);
+            let decoded = decode(&bytes, None).unwrap();
+            assert_eq!(decoded.text, XML);
+            assert_eq!(
+                decoded.encoding,
+                if big_endian { UTF_16BE } else { UTF_16LE }
+            );
+            assert_eq!(decoded.encode(&decoded.text), bytes);
+        }
+    }
+
+    #[test]
+    fn windows_1252_by_declaration_and_as_fallback() {
+        let declared =
+            b"<?xml version=\"1.0\" encoding=\"windows-1252\"?><PTS title=\"\xC9t\xE9\"/>";
+        let decoded = decode(declared, None).unwrap();
+        assert_eq!(decoded.encoding, WINDOWS_1252);
+        assert!(decoded.text.contains("Été"));
+
+        let lying = b"<?xml version=\"1.0\" encoding=\"UTF-8\"?><PTS title=\"\xC9t\xE9\"/>";
+        let decoded = decode(lying, None).unwrap();
+        assert_eq!(decoded.encoding, WINDOWS_1252);
+        assert_eq!(decoded.encode(&decoded.text), lying);
+    }
+
+    #[test]
+    fn forced_encoding_wins() {
+        let decoded = decode("Été".as_bytes(), Some("cp1252")).unwrap();
+        assert_eq!(decoded.text, "Ã‰tÃ©");
+        assert!(decode(b"x", Some("klingon")).is_err());
+    }
+
+    #[test]
+    fn declaration_is_rewritten_to_utf8() {
+        let text = "<?xml version=\"1.0\" encoding=\'windows-1252\'?><PTS/>";
+        assert_eq!(
+            declare_utf8(text),
+            "<?xml version=\"1.0\" encoding=\'UTF-8\'?><PTS/>"
+        );
+        assert_eq!(declare_utf8("<PTS/>"), "<PTS/>");
+    }
+}
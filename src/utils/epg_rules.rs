@@ -0,0 +1,121 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub const DEFAULT_RULES: &str = include_str!("epg_rules.json");
+
+// Text rules for the siStandard descriptions, the texts are named by their
+// attributes: eventName, shortDescription and longDescription.
+#[derive(Deserialize)]
+pub struct EpgRules {
+    // characters
+    #[serde(default)]
+    pub max_lengths: HashMap<String, usize>,
+    #[serde(default)]
+    pub required: Vec<String>,
+    // languageCode of services without their own
+    pub language: Option<String>,
+    // serviceId -> languageCode
+    #[serde(default)]
+    pub service_languages: HashMap<String, String>,
+    // matched as whole words, ignoring case
+    #[serde(default)]
+    pub placeholders: Vec<String>,
+    // characters the playout encodes although they are not in the table
+    #[serde(default)]
+    pub extra_characters: String,
+}
+
+// The default DVB character table (ISO/IEC 6937 with the euro sign) holds
+// Latin-1, Latin Extended-A and a few symbols.
+fn in_dvb_table(c: char) -> bool {
+    matches!(c, ' '..='~' | '\n' | '\r' | '\u{a0}'..='\u{ff}' | '\u{100}'..='\u{17f}')
+        || "‘’“”€™♪Ω←↑→↓―⅛⅜⅝⅞".contains(c)
+}
+
+fn contains_word(text: &str, word: &str) -> bool {
+    let text = text.to_lowercase();
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return false;
+    }
+    text.match_indices(&word).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + word.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+impl EpgRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("epg_rules.json is valid")
+    }
+
+    pub fn max_length(&self, field: &str) -> Option<usize> {
+        self.max_lengths.get(field).copied()
+    }
+
+    pub fn is_required(&self, field: &str) -> bool {
+        self.required.iter().any(|x| x == field)
+    }
+
+    pub fn language(&self, service_id: &str) -> Option<&str> {
+        self.service_languages
+            .get(service_id.trim())
+            .or(self.language.as_ref())
+            .map(String::as_str)
+    }
+
+    // the characters of `text` the playout cannot encode, each once
+    pub fn unencodable(&self, text: &str) -> String {
+        let mut result = String::new();
+        for c in text.chars() {
+            if !in_dvb_table(c) && !self.extra_characters.contains(c) && !result.contains(c) {
+                result.push(c);
+            }
+        }
+        result
+    }
+
+    pub fn placeholder(&self, text: &str) -> Option<&str> {
+        self.placeholders
+            .iter()
+            .find(|x| contains_word(text, x))
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_whole_words() {
+        let rules = EpgRules::default_rules();
+        assert_eq!(rules.placeholder("Text folgt."), Some("Text folgt"));
+        assert_eq!(rules.placeholder("Termin: tba"), Some("TBA"));
+        assert_eq!(rules.placeholder("Die Tbatz-Saga"), None);
+        assert_eq!(rules.placeholder("XXXL Möbel"), None);
+    }
+
+    #[test]
+    fn characters_outside_of_the_dvb_table() {
+        let mut rules = EpgRules::default_rules();
+        assert_eq!(rules.unencodable("Straße, Łódź, „Zitat“ – 5 €"), "„–");
+        assert_eq!(rules.unencodable("Film 🎬🎬 ✓"), "🎬✓");
+        rules.extra_characters = "–".to_string();
+        assert_eq!(rules.unencodable("A – B"), "");
+    }
+
+    #[test]
+    fn languages_per_service() {
+        let mut rules = EpgRules::default_rules();
+        rules
+            .service_languages
+            .insert("UHD2".to_string(), "eng".to_string());
+        assert_eq!(rules.language(" UHD2 "), Some("eng"));
+        assert_eq!(rules.language("UHD1"), Some("deu"));
+        rules.language = None;
+        assert_eq!(rules.language("UHD1"), None);
+    }
+}
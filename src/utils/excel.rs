@@ -35,11 +35,19 @@ impl Excel {
         Ok(())
     }
 
-    pub fn write_file(&mut self, data: &Vec<Vec<String>>) -> Result<(), XlsxError> {
+    pub fn add_sheet(
+        &mut self,
+        name: Option<&str>,
+        data: &Vec<Vec<String>>,
+        header: &Vec<String>,
+    ) -> Result<(), XlsxError> {
         let mut worksheet = self.workbook.add_worksheet();
+        if let Some(name) = name {
+            worksheet.set_name(name)?;
+        }
 
         for (row, value) in data.iter().enumerate() {
-            if value[0] == "title" {
+            if value == header {
                 for (col, value) in value.iter().enumerate() {
                     Excel::store_heading(&mut worksheet, row as u32, col as u16, value)?;
                 }
@@ -49,7 +57,10 @@ impl Excel {
                 }
             }
         }
+        Ok(())
+    }
 
+    pub fn save(&mut self) -> Result<(), XlsxError> {
         self.workbook.save(&self.path)?;
         Ok(())
     }
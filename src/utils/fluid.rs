@@ -1,5 +1,6 @@
 use crate::pts_loader::event::*;
 use crate::pts_loader::sistandard::*;
+use crate::utils::messages::Msg;
 use csv::Reader;
 use csv::ReaderBuilder;
 use serde::{Deserialize, Serialize};
@@ -11,10 +12,12 @@ use std::process::{Command, Output};
 pub enum QueryType {
     Filename,
     Duration,
+    Class,
+    Column(String),
 }
 
 pub fn download_fluid_data_base(file_name: &str) -> Result<String, Box<dyn Error>> {
-    println!("Trying to download fluid database");
+    println!("{}", Msg::DownloadingFluid);
     let venv_activation_script = if cfg!(target_os = "windows") {
         r"fluid_downloader\venv\Scripts\activate.bat" // Use .bat for Windows
     } else {
@@ -47,7 +50,9 @@ pub fn download_fluid_data_base(file_name: &str) -> Result<String, Box<dyn Error
     let stdout = String::from_utf8_lossy(&output.stdout);
     //println!("stdout: {:?}", stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
-    println!("stderr: {:?}", stderr);
+    if !stderr.trim().is_empty() {
+        println!("{}", Msg::DownloadStderr.fill(&[&stderr]));
+    }
 
     let mut res = String::from("");
     for line in stdout.lines() {
@@ -64,7 +69,7 @@ fn read_csv(path: &String) -> Option<Reader<File>> {
         let rdr = ReaderBuilder::new().delimiter(delimiter).from_path(path);
         match rdr {
             Err(err) => {
-                println!("{}: {}", "Error occured", err);
+                println!("{}", Msg::FluidReadError.fill(&[&err]));
                 break;
             }
             Ok(mut rdr) => {
@@ -117,7 +122,21 @@ impl Fluid {
         Fluid { database: vec![] }
     }
 
-    #[allow(dead_code)]
+    // one line per entry, the columns as (name, value)
+    #[cfg(test)]
+    pub fn from_entries(entries: &[&[(&str, &str)]]) -> Self {
+        let database = entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+                    .collect()
+            })
+            .collect();
+        Fluid { database }
+    }
+
     pub fn size(&self) -> usize {
         self.database.len()
     }
@@ -133,7 +152,7 @@ impl Fluid {
                 println!("{:?}", e);
             }
         } else {
-            println!("Error: Database has length {}", *&self.database.len());
+            println!("{}", Msg::FluidDatabaseLength.fill(&[&self.database.len()]));
         }
     }
 
@@ -167,18 +186,28 @@ impl Fluid {
         }
     }
 
+    // the fluid line of the event's content id
+    pub fn entry(&self, event: &Event) -> Option<&HashMap<String, String>> {
+        let id = event.get_contentid();
+        if id.is_empty() {
+            return None;
+        }
+        self.database.iter().find(|entry| {
+            entry
+                .get("ContentId")
+                .is_some_and(|x| !x.is_empty() && (x.contains(&id) || id.contains(x.as_str())))
+        })
+    }
+
     #[allow(dead_code)]
     pub fn query(&self, event: &Event, qtype: QueryType) -> Option<String> {
-        let id = event.get_contentid();
-        for entry in &self.database {
-            if entry["ContentId"].contains(&id) || id.contains(&entry["ContentId"]) {
-                match qtype {
-                    QueryType::Filename => return Some(format!("{}", entry["Filename"])),
-                    QueryType::Duration => return Some(format!("{}", entry["RuntimeMs"])),
-                }
-            }
+        let entry = self.entry(event)?;
+        match qtype {
+            QueryType::Filename => Some(format!("{}", entry["Filename"])),
+            QueryType::Duration => Some(format!("{}", entry["RuntimeMs"])),
+            QueryType::Class => entry.get("Class").cloned(),
+            QueryType::Column(column) => entry.get(&column).cloned(),
         }
-        None
     }
 
     #[allow(dead_code)]
@@ -0,0 +1,40 @@
+use serde::Deserialize;
+
+pub const DEFAULT_RULES: &str = include_str!("format_rules.json");
+
+// The fluid `Res.` and `Format` values allowed on a service, an empty list
+// allows everything.
+#[derive(Deserialize)]
+pub struct ServiceFormat {
+    pub service_id: String,
+    #[serde(default)]
+    pub resolutions: Vec<String>,
+    #[serde(default)]
+    pub formats: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FormatRules {
+    pub services: Vec<ServiceFormat>,
+}
+
+// `value` is one of `values`, ignoring case
+pub fn allowed(value: &str, values: &[String]) -> bool {
+    values.is_empty()
+        || values
+            .iter()
+            .any(|x| x.trim().eq_ignore_ascii_case(value.trim()))
+}
+
+impl FormatRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("format_rules.json is valid")
+    }
+
+    pub fn service(&self, service_id: &str) -> Option<&ServiceFormat> {
+        self.services
+            .iter()
+            .find(|service| service.service_id.trim() == service_id.trim())
+    }
+}
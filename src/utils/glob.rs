@@ -0,0 +1,60 @@
+// '*' matches any number of characters, '?' one character and '#' a digit
+//
+// after a mismatch only the last '*' takes one more character, earlier
+// ones never need to, so this runs in O(pattern * text) and not in
+// exponential time like trying every split
+pub fn matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // the position after the last '*' and the text it was tried with
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || (*c == '#' && text[t].is_ascii_digit()) || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((after, tried)) => {
+                    star = Some((after, tried + 1));
+                    p = after;
+                    t = tried + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("P###-#", "P100-1"));
+        assert!(!matches("P###-#", "P10-1"));
+        assert!(matches("P*", "P"));
+        assert!(matches("P*-?", "P100-A"));
+        assert!(!matches("P*-?", "P100-12"));
+        assert!(matches("*", ""));
+        assert!(matches("*.pts", "montag.pts"));
+        assert!(matches("mo*g.pts", "montag.pts"));
+        assert!(!matches("*.pts", "montag.xml"));
+        assert!(matches("*é*", "sommer_été.pts"));
+        assert!(matches("*a*b", "xaxxab"));
+    }
+
+    #[test]
+    fn many_stars_do_not_backtrack() {
+        let text = "a".repeat(100);
+        assert!(!matches(&format!("{}b", "*a".repeat(30)), &text));
+        assert!(matches(&"*a".repeat(30), &text));
+    }
+}
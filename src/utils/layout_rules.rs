@@ -0,0 +1,96 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub const DEFAULT_RULES: &str = include_str!("layout_rules.json");
+
+// Which screen areas a layout or logo occupies. Two of them overlapping in
+// time conflict if they share an area, unless the pair is listed as
+// compatible. Pairs listed as conflicting always conflict.
+#[derive(Deserialize)]
+pub struct LayoutRules {
+    areas: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    compatible: Vec<(String, String)>,
+    #[serde(default)]
+    conflicting: Vec<(String, String)>,
+}
+
+pub enum Conflict {
+    Area(String),
+    Rule,
+}
+
+fn listed(pairs: &[(String, String)], a: &str, b: &str) -> bool {
+    pairs
+        .iter()
+        .any(|(x, y)| (x == a && y == b) || (x == b && y == a))
+}
+
+impl LayoutRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("layout_rules.json is valid")
+    }
+
+    pub fn conflict(&self, a: &str, b: &str) -> Option<Conflict> {
+        if listed(&self.conflicting, a, b) {
+            return Some(Conflict::Rule);
+        }
+        if listed(&self.compatible, a, b) {
+            return None;
+        }
+        let areas = self.areas.get(b)?;
+        self.areas
+            .get(a)?
+            .iter()
+            .find(|area| areas.contains(area))
+            .map(|area| Conflict::Area(area.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(json: &str) -> LayoutRules {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn area(conflict: Option<Conflict>) -> Option<String> {
+        match conflict? {
+            Conflict::Area(area) => Some(area),
+            Conflict::Rule => Some("rule".to_string()),
+        }
+    }
+
+    #[test]
+    fn shared_areas_conflict() {
+        let rules = LayoutRules::default_rules();
+        let conflict = rules.conflict("HDPLUHD_LAY_1", "HDPLUHD_LOGO_2");
+        assert_eq!(area(conflict).as_deref(), Some("top_right"));
+        let conflict = rules.conflict("HDPLUHD_LAY_10", "HDPLUHD_LAY_11");
+        assert_eq!(area(conflict).as_deref(), Some("lower_third"));
+        assert!(rules.conflict("HDPLUHD_LAY_1", "HDPLUHD_LOGO_3").is_none());
+    }
+
+    #[test]
+    fn unknown_ids_never_conflict() {
+        let rules = LayoutRules::default_rules();
+        assert!(rules.conflict("HDPLUHD_LAY_1", "UNKNOWN").is_none());
+        assert!(rules.conflict("UNKNOWN", "HDPLUHD_LAY_1").is_none());
+    }
+
+    #[test]
+    fn listed_pairs_override_the_areas() {
+        let rules = rules(
+            r#"{
+                "areas": {"A": ["top"], "B": ["top"], "C": ["bottom"]},
+                "compatible": [["B", "A"]],
+                "conflicting": [["A", "C"]]
+            }"#,
+        );
+        assert!(rules.conflict("A", "B").is_none());
+        assert_eq!(area(rules.conflict("C", "A")).as_deref(), Some("rule"));
+        assert!(rules.conflict("B", "C").is_none());
+    }
+}
@@ -0,0 +1,726 @@
+use chrono::Weekday;
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Lang {
+    De,
+    En,
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+impl Lang {
+    pub fn parse(s: &str) -> Option<Lang> {
+        let s = s.trim().to_lowercase();
+        if s.starts_with("de") {
+            Some(Lang::De)
+        } else if s.starts_with("en") {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+
+    // LC_ALL, LC_MESSAGES and LANG win over the system locale, like gettext does
+    pub fn detect() -> Lang {
+        for var in ["LC_ALL", "LC_MESSAGES", "LANG"] {
+            if let Some(lang) = std::env::var(var).ok().as_deref().and_then(Lang::parse) {
+                return lang;
+            }
+        }
+        sys_locale::get_locale()
+            .as_deref()
+            .and_then(Lang::parse)
+            .unwrap_or(Lang::En)
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(Lang::detect)
+}
+
+pub fn weekday(day: Weekday) -> &'static str {
+    let (de, en) = match day {
+        Weekday::Mon => ("Montag", "Monday"),
+        Weekday::Tue => ("Dienstag", "Tuesday"),
+        Weekday::Wed => ("Mittwoch", "Wednesday"),
+        Weekday::Thu => ("Donnerstag", "Thursday"),
+        Weekday::Fri => ("Freitag", "Friday"),
+        Weekday::Sat => ("Samstag", "Saturday"),
+        Weekday::Sun => ("Sonntag", "Sunday"),
+    };
+    match lang() {
+        Lang::De => de,
+        Lang::En => en,
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Msg {
+    // main
+    FileNotFound,
+    NoFileGiven,
+    DownloadSuccess,
+    DownloadStderr,
+    DownloadingFluid,
+    FluidReadError,
+    FluidDatabaseLength,
+    WroteCsv,
+    WroteXlsx,
+    UpdatedPts,
+    SiEvents,
+    Trailer,
+    AllContentIds,
+    EncodingNotAvailable,
+    UnknownLang,
+    InvalidRange,
+    UnknownEncoding,
+    CannotUpdateStdin,
+    NoEntries,
+    EntryError,
+    DayFormat,
+    UnknownColumn,
+    // summary
+    ErrorSummary,
+    TimeErrors,
+    IdErrors,
+    LogoErrors,
+    SpecialEventErrors,
+    LengthErrors,
+    SiLengthErrors,
+    CommercialLengthErrors,
+    VaErrors,
+    SiErrors,
+    MissingTexts,
+    TrailerBallsMixup,
+    ContentTooLong,
+    PufferschleifeErrors,
+    AlteTafelErrors,
+    UseDranbleiben,
+    UseBaldGehtsWeiter,
+    // reports
+    SpecialEvents,
+    MissingEnd,
+    MissingBegin,
+    MissingLogo,
+    AlteTafel,
+    ContentLengthError,
+    VaAndIdErrors,
+    MissingTextsHeading,
+    IllegalEventsFound,
+    IllegalVaEventsFound,
+    IllegalSiEventsFound,
+    IllegalLogoEventsFound,
+    IllegalLayoutEventsFound,
+    IllegalEvents,
+    TimeGap,
+    TimeOverlap,
+    PlanDeviations,
+    PlanMissing,
+    PlanExtra,
+    PlanReordered,
+    PlanLength,
+    PlanLoadError,
+    PlanNoSheets,
+    PlanInvalidDuration,
+    PlanNoHeader,
+    WerbungenDeprecated,
+    PlanMissingErrors,
+    PlanExtraErrors,
+    PlanOrderErrors,
+    PlanLengthErrors,
+    LogoCoverage,
+    LogoMissing,
+    LogoLateStart,
+    LogoEarlyEnd,
+    LogoGap,
+    LogoIntoBreak,
+    LogoCoverageErrors,
+    LayoutConflicts,
+    LayoutConflictErrors,
+    LayoutRule,
+    LayoutRulesError,
+    ComplianceErrorsHeading,
+    ComplianceErrors,
+    ComplianceUncovered,
+    ComplianceMissingAtStart,
+    ComplianceMissingAtEnd,
+    ComplianceRulesError,
+    ComplianceSheet,
+    AdvertisingPerWindow,
+    AdvertisingErrors,
+    AdvertisingSheet,
+    Interruptions,
+    InterruptionShortSegment,
+    InterruptionBreaksTooClose,
+    InterruptionErrors,
+    YouthProtection,
+    YouthTooEarly,
+    YouthRunsOutside,
+    YouthTrailer,
+    YouthErrors,
+    YouthRulesError,
+    Identifiers,
+    IdDuplicateEventId,
+    IdEventIdReused,
+    IdMalformedProgramId,
+    IdMixedServiceId,
+    IdMissingContentId,
+    IdentifierErrors,
+    RerunsByContentId,
+    RerunsByTitle,
+    RerunTooClose,
+    RerunTooOften,
+    RerunErrors,
+    RerunRulesError,
+    DayCoverage,
+    DayCoverageErrors,
+    Sendepausen,
+    PauseLoopMissing,
+    PauseLoopStartsLate,
+    PauseLoopEndsEarly,
+    PauseLoopGap,
+    PauseNotLoop,
+    PauseLoopOutside,
+    PauseLoopTooLong,
+    PauseForbidden,
+    PauseErrors,
+    PauseRulesError,
+    OffsetErrorsHeading,
+    OffsetOverlap,
+    OffsetOutOfOrder,
+    OffsetErrors,
+    MaterialErrorsHeading,
+    MaterialUnknown,
+    MaterialInDustbin,
+    MaterialUnregistered,
+    MaterialErrors,
+    MaterialSheet,
+    FormatErrorsHeading,
+    FormatResolution,
+    FormatFormat,
+    FormatErrors,
+    FormatRulesError,
+    BallErrorsHeading,
+    BallBefore,
+    BallAfter,
+    BallRulesError,
+    BallSheet,
+    EpgTextHeading,
+    EpgMissing,
+    EpgTooLong,
+    EpgNotEncodable,
+    EpgLanguage,
+    EpgPlaceholder,
+    EpgTextErrors,
+    EpgRulesError,
+    TitleErrorsHeading,
+    TitleEpgMismatch,
+    TitleNotInEpg,
+    TitleErrors,
+    BlockErrorsHeading,
+    BlockNested,
+    BlockNoCommercials,
+    BlockCrossesSiEvent,
+    BlockRulesError,
+    SiFixHeading,
+    SiFixNone,
+    SiUnfixedHeading,
+    WroteFixedPts,
+    // columns
+    ColDay,
+    ColTitle,
+    ColFilename,
+    ColProgramId,
+    ColStart,
+    ColEnd,
+    ColDuration,
+    ColTcin,
+    ColTcout,
+    ColContentId,
+    ColLogo,
+    ColFluidDuration,
+    ColBlock,
+    ColIssue,
+    ColPlanned,
+    ColScheduled,
+    ColRow,
+    ColProgramme,
+    ColFirst,
+    ColSecond,
+    ColArea,
+    ColRule,
+    ColUncovered,
+    ColAdvertising,
+    ColLimit,
+    ColShare,
+    ColLength,
+    ColMinimum,
+    ColRating,
+    ColWindow,
+    ColType,
+    ColEventId,
+    ColServiceId,
+    ColDetail,
+    ColClass,
+    ColAirings,
+    ColDays,
+    ColPerDay,
+    ColMinGap,
+    ColExpected,
+    ColMissing,
+    ColLeading,
+    ColTrailing,
+    ColOverrun,
+    ColOffsets,
+    ColPrevious,
+    ColOverlap,
+    ColMaterialId,
+    ColValue,
+    ColAllowed,
+    ColNeighbour,
+    ColField,
+    ColEpgTitle,
+    ColSimilarity,
+    ColRelated,
+    ColOld,
+    ColNew,
+}
+
+impl Msg {
+    pub fn text(self) -> &'static str {
+        let (de, en) = match self {
+            Msg::FileNotFound => (
+                "Das System kann die angegebene Datei {} nicht finden.",
+                "The system cannot find the file {}.",
+            ),
+            Msg::NoFileGiven => ("Keine Datei angegeben", "No file given"),
+            Msg::DownloadSuccess => ("Erfolgreich: {}", "success: {}"),
+            Msg::DownloadStderr => ("Fehlerausgabe: {}", "stderr: {}"),
+            Msg::DownloadingFluid => (
+                "Versuche die Fluid-Datenbank herunterzuladen",
+                "Trying to download fluid database",
+            ),
+            Msg::FluidReadError => (
+                "Fehler beim Lesen der Fluid-Datenbank: {}",
+                "Error reading the fluid database: {}",
+            ),
+            Msg::FluidDatabaseLength => (
+                "Fehler: Datenbank hat die Länge {}",
+                "Error: Database has length {}",
+            ),
+            Msg::WroteCsv => ("CSV nach '{}' geschrieben", "Wrote csv to '{}'"),
+            Msg::WroteXlsx => ("XLSX nach '{}' geschrieben", "Wrote xlsx to '{}'"),
+            Msg::UpdatedPts => ("PTS-Datei aktualisiert: {}", "updated pts file: {}"),
+            Msg::SiEvents => ("SI-Events", "SiEvents"),
+            Msg::Trailer => ("Trailer", "Trailer"),
+            Msg::AllContentIds => ("Alle Content-IDs", "All content ids"),
+            Msg::EncodingNotAvailable => (
+                "Verfügbare Kodierungen sind nur utf-8 und windows1252\nKeine Datei geschrieben.",
+                "only available encodings are utf-8 and windows1252\nNo file written.",
+            ),
+            Msg::UnknownLang => (
+                "unbekannte Sprache {}, de oder en verwenden",
+                "unknown language {}, use de or en",
+            ),
+            Msg::InvalidRange => ("Ungültiger Zeitraum {}", "Invalid range {}"),
+            Msg::UnknownEncoding => (
+                "unbekannte Kodierung {}, z.B. utf-8, windows-1252 oder utf-16 verwenden",
+                "unknown encoding {}, use e.g. utf-8, windows-1252 or utf-16",
+            ),
+            Msg::CannotUpdateStdin => (
+                "von stdin gelesene Dateien können nicht aktualisiert werden",
+                "a file read from stdin can not be updated",
+            ),
+            Msg::NoEntries => (
+                "{} enthält keine passenden PTS-Dateien",
+                "{} contains no matching pts files",
+            ),
+            Msg::EntryError => ("{} konnte nicht gelesen werden: {}", "could not read {}: {}"),
+            Msg::DayFormat => (
+                "erwartetes Format ist tt.mm.jjjj",
+                "required format is dd.mm.yyyy",
+            ),
+            Msg::UnknownColumn => (
+                "unbekannte Spalte {}, verfügbare Spalten: {}",
+                "unknown column {}, available columns: {}",
+            ),
+            Msg::ErrorSummary => ("Fehlerübersicht:", "Error Summary:"),
+            Msg::TimeErrors => ("Zeitfehler", "time errors"),
+            Msg::IdErrors => ("ID-Fehler", "id errors"),
+            Msg::LogoErrors => ("Logofehler", "logo errors"),
+            Msg::SpecialEventErrors => ("Sonderevent-Fehler", "special event errors"),
+            Msg::LengthErrors => ("Längenfehler", "length errors"),
+            Msg::SiLengthErrors => ("SI-Längenfehler", "si length errors"),
+            Msg::CommercialLengthErrors => ("Werbelängenfehler", "commercial length errors"),
+            Msg::VaErrors => ("vaEvent-Fehler", "vaerrors"),
+            Msg::SiErrors => ("siEvent-Fehler", "sierrors"),
+            Msg::MissingTexts => ("fehlende Texte", "missing texts"),
+            Msg::TrailerBallsMixup => ("Trailer/Ball vertauscht", "trailer balls mixup"),
+            Msg::ContentTooLong => ("zu langer Content", "content which is too long"),
+            Msg::PufferschleifeErrors => ("Pufferschleifen-Fehler", "buffer loop errors"),
+            Msg::AlteTafelErrors => ("Alte-Tafel-Fehler", "old board errors"),
+            Msg::UseDranbleiben => (
+                "bitte 'Dranbleiben - gleich geht's lo' 3ecea6b8084f35c2634d verwenden",
+                "please use 'Dranbleiben - gleich geht's lo' 3ecea6b8084f35c2634d",
+            ),
+            Msg::UseBaldGehtsWeiter => (
+                "bitte 'Bald geht's hier weiter mit de' 02e9d82071c7735de5cd verwenden",
+                "please use 'Bald geht's hier weiter mit de' 02e9d82071c7735de5cd",
+            ),
+            Msg::SpecialEvents => ("Sonderevents:", "Special events:"),
+            Msg::MissingEnd => ("fehlendes Ende", "missing end"),
+            Msg::MissingBegin => ("fehlender Anfang", "missing begin"),
+            Msg::MissingLogo => ("Logo fehlt", "Missing logo"),
+            Msg::AlteTafel => ("ALTE TAFEL", "OLD BOARD"),
+            Msg::ContentLengthError => ("Content-Längenfehler", "Content length error"),
+            Msg::VaAndIdErrors => (
+                "vaEvent-Fehler und ID-Fehler:",
+                "VaEvent errors and id errors:",
+            ),
+            Msg::MissingTextsHeading => ("Fehlende Texte:", "Missing texts:"),
+            Msg::IllegalEventsFound => ("unzulässige Events gefunden.", "illegal events found."),
+            Msg::IllegalVaEventsFound => (
+                "unzulässige vaEvents gefunden",
+                "illegal vaEvents found",
+            ),
+            Msg::IllegalSiEventsFound => (
+                "unzulässige siEvents gefunden",
+                "illegal siEvents found",
+            ),
+            Msg::IllegalLogoEventsFound => (
+                "unzulässige logoEvents gefunden",
+                "illegal logoEvents found",
+            ),
+            Msg::IllegalLayoutEventsFound => (
+                "unzulässige layoutEvents gefunden",
+                "illegal layoutEvents found",
+            ),
+            Msg::IllegalEvents => ("Unzulässige Events:", "Illegal events:"),
+            Msg::TimeGap => ("Zeitlücke", "Time gap"),
+            Msg::TimeOverlap => ("Zeitüberlappung", "Time overlap"),
+            Msg::PlanDeviations => ("Abweichungen von der Planung:", "Deviations from the plan:"),
+            Msg::PlanMissing => ("fehlt im Sendeplan", "missing in schedule"),
+            Msg::PlanExtra => ("nicht geplant", "not planned"),
+            Msg::PlanReordered => ("falsche Reihenfolge", "wrong order"),
+            Msg::PlanLength => ("falsche Länge", "wrong length"),
+            Msg::PlanLoadError => (
+                "Planung {} konnte nicht gelesen werden: {}",
+                "could not read plan {}: {}",
+            ),
+            Msg::PlanNoSheets => (
+                "die Arbeitsmappe hat keine Tabellenblätter",
+                "workbook has no sheets",
+            ),
+            Msg::PlanInvalidDuration => (
+                "Zeile {}: ungültige Dauer {}",
+                "row {}: invalid duration {}",
+            ),
+            Msg::WerbungenDeprecated => (
+                "--werbungen ist veraltet, bitte `check --plan <Planung>` verwenden",
+                "--werbungen is deprecated, use `check --plan <workbook>` instead",
+            ),
+            Msg::PlanNoHeader => (
+                "keine Kopfzeile mit Titel- und Dauer-Spalte im Tabellenblatt {}",
+                "no header row with title and duration columns found in sheet {}",
+            ),
+            Msg::PlanMissingErrors => ("fehlende Spots", "missing spots"),
+            Msg::PlanExtraErrors => ("ungeplante Spots", "unplanned spots"),
+            Msg::PlanOrderErrors => ("Spots in falscher Reihenfolge", "spots in wrong order"),
+            Msg::PlanLengthErrors => ("Spots mit falscher Länge", "spots with wrong length"),
+            Msg::LogoCoverage => ("Lücken in der Logoabdeckung:", "Gaps in logo coverage:"),
+            Msg::LogoMissing => ("kein Logo", "no logo"),
+            Msg::LogoLateStart => ("Logo beginnt zu spät", "logo starts late"),
+            Msg::LogoEarlyEnd => ("Logo endet zu früh", "logo ends early"),
+            Msg::LogoGap => ("Lücke im Logo", "gap in logo"),
+            Msg::LogoIntoBreak => ("Logo läuft in die Werbung", "logo runs into break"),
+            Msg::LogoCoverageErrors => ("Logoabdeckungsfehler", "logo coverage errors"),
+            Msg::LayoutConflicts => (
+                "Überlappende Layouts und Logos:",
+                "Conflicting layouts and logos:",
+            ),
+            Msg::LayoutConflictErrors => ("Layoutkonflikte", "layout conflicts"),
+            Msg::LayoutRule => ("Regel", "rule"),
+            Msg::LayoutRulesError => (
+                "Layoutregeln {} konnten nicht gelesen werden: {}",
+                "could not read layout rules {}: {}",
+            ),
+            Msg::ComplianceErrorsHeading => (
+                "Fehlende Kennzeichnung (Dauerwerbesendung/Produktplatzierung):",
+                "Missing labels (infomercial/product placement):",
+            ),
+            Msg::ComplianceErrors => ("Kennzeichnungsfehler", "labelling errors"),
+            Msg::ComplianceUncovered => ("Layout fehlt", "layout missing"),
+            Msg::ComplianceMissingAtStart => (
+                "Layout fehlt am Anfang",
+                "layout missing at start",
+            ),
+            Msg::ComplianceMissingAtEnd => ("Layout fehlt am Ende", "layout missing at end"),
+            Msg::ComplianceRulesError => (
+                "Kennzeichnungsregeln {} konnten nicht gelesen werden: {}",
+                "could not read labelling rules {}: {}",
+            ),
+            Msg::ComplianceSheet => ("Kennzeichnung", "Labelling"),
+            Msg::AdvertisingPerWindow => (
+                "Werbezeit pro {} Minuten:",
+                "Advertising time per {} minutes:",
+            ),
+            Msg::AdvertisingErrors => (
+                "Zeitfenster über der Werbegrenze",
+                "windows over the advertising limit",
+            ),
+            Msg::AdvertisingSheet => ("Werbezeit", "Advertising"),
+            Msg::Interruptions => (
+                "Zu früh unterbrochene Sendungen:",
+                "Programmes interrupted too early:",
+            ),
+            Msg::InterruptionShortSegment => ("Teil zu kurz", "segment too short"),
+            Msg::InterruptionBreaksTooClose => (
+                "Unterbrechungen zu dicht",
+                "breaks too close together",
+            ),
+            Msg::InterruptionErrors => ("Unterbrechungsfehler", "interruption errors"),
+            Msg::YouthProtection => (
+                "Jugendschutz, Sendungen außerhalb ihrer Sendezeit:",
+                "Youth protection, material outside of its airtime:",
+            ),
+            Msg::YouthTooEarly => ("beginnt zu früh", "starts too early"),
+            Msg::YouthRunsOutside => ("läuft zu lange", "runs past the window"),
+            Msg::YouthTrailer => ("Trailer zu früh", "trailer too early"),
+            Msg::YouthErrors => ("Jugendschutzfehler", "youth protection errors"),
+            Msg::YouthRulesError => (
+                "Jugendschutzregeln konnten nicht geladen werden: {}",
+                "Could not load the youth protection rules: {}",
+            ),
+            Msg::Identifiers => ("Fehlerhafte Kennungen:", "Broken identifiers:"),
+            Msg::IdDuplicateEventId => ("eventId doppelt", "duplicate eventId"),
+            Msg::IdEventIdReused => (
+                "eventId für anderen Inhalt",
+                "eventId used for other content",
+            ),
+            Msg::IdMalformedProgramId => ("programId fehlerhaft", "malformed programId"),
+            Msg::IdMixedServiceId => ("andere serviceId", "different serviceId"),
+            Msg::IdMissingContentId => ("contentId fehlt", "contentId missing"),
+            Msg::IdentifierErrors => ("Kennungsfehler", "identifier errors"),
+            Msg::RerunsByContentId => ("Wiederholungen pro contentId:", "Reruns per contentId:"),
+            Msg::RerunsByTitle => ("Wiederholungen pro Titel:", "Reruns per title:"),
+            Msg::RerunTooClose => ("zu kurz hintereinander", "repeated too soon"),
+            Msg::RerunTooOften => ("zu oft am Tag", "too often a day"),
+            Msg::RerunErrors => ("Wiederholungsfehler", "rerun errors"),
+            Msg::RerunRulesError => (
+                "Wiederholungsregeln {} konnten nicht geladen werden: {}",
+                "Could not load the rerun rules {}: {}",
+            ),
+            Msg::DayCoverage => ("Abdeckung der Sendetage:", "Coverage of the broadcast days:"),
+            Msg::DayCoverageErrors => (
+                "Lücken am Anfang oder Ende eines Sendetags",
+                "holes at the start or end of a broadcast day",
+            ),
+            Msg::Sendepausen => ("Fehler in Sendepausen:", "Errors in Sendepausen:"),
+            Msg::PauseLoopMissing => ("keine Schleife", "no loop"),
+            Msg::PauseLoopStartsLate => ("Schleife beginnt zu spät", "loop starts late"),
+            Msg::PauseLoopEndsEarly => ("Schleife endet zu früh", "loop ends early"),
+            Msg::PauseLoopGap => ("Lücke in der Schleife", "gap in loop"),
+            Msg::PauseNotLoop => ("keine Schleife in Sendepause", "not a loop in Sendepause"),
+            Msg::PauseLoopOutside => (
+                "Schleife außerhalb der Sendepause",
+                "loop outside of Sendepause",
+            ),
+            Msg::PauseLoopTooLong => ("Schleife zu lang", "loop too long"),
+            Msg::PauseForbidden => ("in Sendepause nicht erlaubt", "not allowed in Sendepause"),
+            Msg::PauseErrors => ("Sendepausenfehler", "Sendepause errors"),
+            Msg::PauseRulesError => (
+                "Sendepausenregeln {} konnten nicht geladen werden: {}",
+                "Could not load the Sendepause rules {}: {}",
+            ),
+            Msg::OffsetErrorsHeading => (
+                "Segmente mit überlappenden Offsets:",
+                "Segments with overlapping offsets:",
+            ),
+            Msg::OffsetOverlap => ("überlappt vorheriges Segment", "overlaps previous segment"),
+            Msg::OffsetOutOfOrder => ("vor vorherigem Segment", "before previous segment"),
+            Msg::OffsetErrors => ("Offsetfehler", "offset errors"),
+            Msg::MaterialErrorsHeading => (
+                "vaEvents mit unbekanntem oder unbrauchbarem Material:",
+                "vaEvents with unknown or unusable material:",
+            ),
+            Msg::MaterialUnknown => ("nicht in fluid", "not in fluid"),
+            Msg::MaterialInDustbin => ("im Papierkorb", "in dustbin"),
+            Msg::MaterialUnregistered => ("Registrierung unvollständig", "registration incomplete"),
+            Msg::MaterialErrors => ("Materialfehler", "material errors"),
+            Msg::MaterialSheet => ("Material", "Material"),
+            Msg::FormatErrorsHeading => (
+                "Material mit unzulässiger Auflösung oder unzulässigem Format:",
+                "Material with a resolution or format not allowed on the service:",
+            ),
+            Msg::FormatResolution => ("Auflösung nicht erlaubt", "resolution not allowed"),
+            Msg::FormatFormat => ("Format nicht erlaubt", "format not allowed"),
+            Msg::FormatErrors => ("Auflösungs-/Formatfehler", "resolution/format errors"),
+            Msg::FormatRulesError => (
+                "Formatregeln {} konnten nicht geladen werden: {}",
+                "Could not load the format rules {}: {}",
+            ),
+            Msg::BallErrorsHeading => (
+                "Ball an falscher Stelle (Trailer/Ball vertauscht):",
+                "Ball in the wrong place (trailer balls mixup):",
+            ),
+            Msg::BallBefore => ("falsches Event vor dem Ball", "wrong event before ball"),
+            Msg::BallAfter => ("falsches Event nach dem Ball", "wrong event after ball"),
+            Msg::BallRulesError => (
+                "Ballregeln {} konnten nicht geladen werden: {}",
+                "Could not load the ball rules {}: {}",
+            ),
+            Msg::BallSheet => ("Ball", "Ball"),
+            Msg::EpgTextHeading => ("Mängel in den EPG-Texten:", "EPG text issues:"),
+            Msg::EpgMissing => ("fehlt", "missing"),
+            Msg::EpgTooLong => ("zu lang", "too long"),
+            Msg::EpgNotEncodable => ("nicht im DVB-Zeichensatz", "not in DVB character table"),
+            Msg::EpgLanguage => ("falsche Sprache", "wrong language"),
+            Msg::EpgPlaceholder => ("Platzhaltertext", "placeholder text"),
+            Msg::EpgTextErrors => ("EPG-Textfehler", "EPG text errors"),
+            Msg::EpgRulesError => (
+                "EPG-Regeln {} konnten nicht geladen werden: {}",
+                "Could not load the EPG rules {}: {}",
+            ),
+            Msg::TitleErrorsHeading => (
+                "EPG-Titel passen nicht zum gespielten Material:",
+                "EPG titles not matching the material played:",
+            ),
+            Msg::TitleEpgMismatch => ("EPG-Titel passt nicht", "EPG title mismatch"),
+            Msg::TitleNotInEpg => ("nicht im EPG", "not in EPG"),
+            Msg::TitleErrors => ("Titelabweichungen", "title mismatches"),
+            Msg::BlockErrorsHeading => ("Fehler in den Blöcken:", "Block errors:"),
+            Msg::BlockNested => ("Block im Block", "block inside block"),
+            Msg::BlockNoCommercials => ("Block ohne Werbung", "block without commercials"),
+            Msg::BlockCrossesSiEvent => ("siEvent beginnt im Block", "siEvent starts in block"),
+            Msg::BlockRulesError => (
+                "Blockregeln {} konnten nicht geladen werden: {}",
+                "Could not load the block rules {}: {}",
+            ),
+            Msg::SiFixHeading => ("Korrekturen der siEvents:", "Corrections of the siEvents:"),
+            Msg::SiFixNone => (
+                "Keine Lücken oder Überlappungen zwischen siEvents",
+                "No gaps or overlaps between siEvents",
+            ),
+            Msg::SiUnfixedHeading => (
+                "Mit dieser Strategie nicht korrigierbar:",
+                "Not correctable with this strategy:",
+            ),
+            Msg::WroteFixedPts => (
+                "Korrigierte PTS-Datei geschrieben: {}",
+                "wrote corrected pts file: {}",
+            ),
+            Msg::ColDay => ("Tag", "day"),
+            Msg::ColTitle => ("Titel", "title"),
+            Msg::ColFilename => ("Dateiname", "filename"),
+            Msg::ColProgramId => ("ProgramId", "programid"),
+            Msg::ColStart => ("Start", "start"),
+            Msg::ColEnd => ("Ende", "end"),
+            Msg::ColDuration => ("Dauer", "duration"),
+            Msg::ColTcin => ("TC-In", "tcin"),
+            Msg::ColTcout => ("TC-Out", "tcout"),
+            Msg::ColContentId => ("ContentId", "contentid"),
+            Msg::ColLogo => ("Logo", "logo"),
+            Msg::ColFluidDuration => ("Dauer laut Datenbank", "database duration"),
+            Msg::ColBlock => ("Block", "block"),
+            Msg::ColIssue => ("Fehler", "issue"),
+            Msg::ColPlanned => ("geplant", "planned"),
+            Msg::ColScheduled => ("gesendet", "scheduled"),
+            Msg::ColRow => ("Zeile", "row"),
+            Msg::ColProgramme => ("Sendung", "programme"),
+            Msg::ColFirst => ("erstes", "first"),
+            Msg::ColSecond => ("zweites", "second"),
+            Msg::ColArea => ("Bereich", "area"),
+            Msg::ColRule => ("Regel", "rule"),
+            Msg::ColUncovered => ("ohne Layout", "uncovered"),
+            Msg::ColAdvertising => ("Werbung", "advertising"),
+            Msg::ColLimit => ("Grenze", "limit"),
+            Msg::ColShare => ("Anteil", "share"),
+            Msg::ColLength => ("Länge", "length"),
+            Msg::ColMinimum => ("Minimum", "minimum"),
+            Msg::ColRating => ("FSK", "rating"),
+            Msg::ColWindow => ("Sendezeit", "window"),
+            Msg::ColType => ("Typ", "type"),
+            Msg::ColEventId => ("eventId", "eventid"),
+            Msg::ColServiceId => ("serviceId", "serviceid"),
+            Msg::ColDetail => ("Details", "detail"),
+            Msg::ColClass => ("Klasse", "class"),
+            Msg::ColAirings => ("Anzahl", "airings"),
+            Msg::ColDays => ("Tage", "days"),
+            Msg::ColPerDay => ("pro Tag", "per day"),
+            Msg::ColMinGap => ("min. Abstand", "min gap"),
+            Msg::ColExpected => ("Soll", "expected"),
+            Msg::ColMissing => ("fehlt", "missing"),
+            Msg::ColLeading => ("Lücke am Anfang", "leading hole"),
+            Msg::ColTrailing => ("Lücke am Ende", "trailing hole"),
+            Msg::ColOverrun => ("zu lang um", "overrun"),
+            Msg::ColOffsets => ("Offsets", "offsets"),
+            Msg::ColPrevious => ("vorheriges Segment", "previous segment"),
+            Msg::ColOverlap => ("Überlappung", "overlap"),
+            Msg::ColMaterialId => ("Material-ID", "material id"),
+            Msg::ColValue => ("Wert", "value"),
+            Msg::ColAllowed => ("erlaubt", "allowed"),
+            Msg::ColNeighbour => ("davor/danach", "neighbour"),
+            Msg::ColField => ("Feld", "field"),
+            Msg::ColEpgTitle => ("EPG-Titel", "EPG title"),
+            Msg::ColSimilarity => ("Ähnlichkeit", "similarity"),
+            Msg::ColRelated => ("bezogen auf", "related"),
+            Msg::ColOld => ("alt", "old"),
+            Msg::ColNew => ("neu", "new"),
+        };
+        match lang() {
+            Lang::De => de,
+            Lang::En => en,
+        }
+    }
+
+    // fills the `{}` placeholders in order
+    pub fn fill(self, args: &[&dyn std::fmt::Display]) -> String {
+        let mut parts = self.text().split("{}");
+        let mut result = parts.next().unwrap_or_default().to_string();
+        for (i, part) in parts.enumerate() {
+            match args.get(i) {
+                Some(arg) => result += &arg.to_string(),
+                None => result += "{}",
+            }
+            result += part;
+        }
+        result
+    }
+}
+
+impl std::fmt::Display for Msg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_takes_codes_and_locales() {
+        assert_eq!(Lang::parse("de"), Some(Lang::De));
+        assert_eq!(Lang::parse(" DE_de.UTF-8"), Some(Lang::De));
+        assert_eq!(Lang::parse("en_US"), Some(Lang::En));
+        assert_eq!(Lang::parse("fr"), None);
+        assert_eq!(Lang::parse(""), None);
+    }
+
+    #[test]
+    fn fill_replaces_placeholders_in_order() {
+        let text = Msg::UnknownColumn.fill(&[&"\"x\"", &7]);
+        assert!(text.contains('7') && text.contains("\"x\""));
+        assert!(!text.contains("{}"));
+    }
+
+    #[test]
+    fn fill_keeps_placeholders_without_arguments() {
+        assert_eq!(Msg::UnknownColumn.fill(&[]).matches("{}").count(), 2);
+    }
+}
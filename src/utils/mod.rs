@@ -1,4 +1,19 @@
 pub mod fluid;
-pub mod table_print;
+pub mod table;
 pub mod take;
+pub mod ball_rules;
+pub mod block_rules;
+pub mod compliance_rules;
+pub mod encoding;
+pub mod epg_rules;
 pub mod excel;
+pub mod format_rules;
+pub mod glob;
+pub mod layout_rules;
+pub mod messages;
+pub mod plan;
+pub mod rerun_rules;
+pub mod rules;
+pub mod sendepause_rules;
+pub mod source;
+pub mod youth_rules;
@@ -0,0 +1,437 @@
+use calamine::{open_workbook_auto, Data, Reader};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use crate::utils::messages::Msg;
+use std::error::Error;
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PlanStart {
+    DateTime(NaiveDateTime),
+    Time(NaiveTime),
+}
+
+#[derive(Clone, Debug)]
+pub struct PlanSpot {
+    pub title: String,
+    pub duration: i64,
+    pub row: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct PlanBreak {
+    pub key: String,
+    pub start: Option<PlanStart>,
+    pub spots: Vec<PlanSpot>,
+}
+
+pub struct Plan {
+    pub breaks: Vec<PlanBreak>,
+}
+
+struct Header {
+    block: Option<usize>,
+    start: Option<usize>,
+    title: usize,
+    duration: usize,
+}
+
+fn find_header(row: &[Data]) -> Option<Header> {
+    let position = |names: &[&str]| {
+        row.iter().position(|cell| {
+            let cell = cell.to_string().trim().to_lowercase();
+            names.iter().any(|name| cell == *name)
+        })
+    };
+    Some(Header {
+        block: position(&["block", "break", "unterbrechung", "werbeblock"]),
+        start: position(&["start", "time", "zeit", "startzeit", "beginn"]),
+        title: position(&["title", "titel", "spot", "motiv"])?,
+        duration: position(&["duration", "dauer", "länge", "laenge", "length"])?,
+    })
+}
+
+fn excel_datetime(value: f64) -> Option<NaiveDateTime> {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_time(NaiveTime::MIN);
+    let ms = (value * MS_PER_DAY).round() as i64;
+    epoch.checked_add_signed(Duration::milliseconds(ms))
+}
+
+// Accepts excel times, seconds, "ss", "mm:ss", "hh:mm:ss", "hh:mm:ss.mmm"
+// and "hh:mm:ss:ff". Numbers below 1 are excel times (a fraction of a day),
+// larger ones seconds, no spot is longer than a day or shorter than a second.
+pub fn parse_duration(cell: &Data, fps: i64) -> Option<i64> {
+    match cell {
+        Data::Int(i) => Some(i * 1000),
+        Data::Float(f) => {
+            if *f < 1.0 {
+                Some((f * MS_PER_DAY).round() as i64)
+            } else {
+                Some((f * 1000.0).round() as i64)
+            }
+        }
+        Data::DateTime(dt) => Some((dt.as_f64().fract() * MS_PER_DAY).round() as i64),
+        Data::String(s) => parse_duration_str(s, fps),
+        _ => None,
+    }
+}
+
+// frames are counted at `fps` frames per second
+pub fn parse_duration_str(s: &str, fps: i64) -> Option<i64> {
+    let s = s.trim().replace(',', ".");
+    let parts: Vec<&str> = s.split(':').collect();
+    let (hours, minutes, seconds, frames) = match parts.len() {
+        1 => ("0", "0", parts[0], None),
+        2 => ("0", parts[0], parts[1], None),
+        3 => (parts[0], parts[1], parts[2], None),
+        4 => (parts[0], parts[1], parts[2], Some(parts[3])),
+        _ => return None,
+    };
+    let hours: i64 = hours.parse().ok()?;
+    let minutes: i64 = minutes.parse().ok()?;
+    let seconds: f64 = seconds.parse().ok()?;
+    let frames: i64 = match frames {
+        None => 0,
+        Some(f) => f.parse::<i64>().ok()? * 1000 / fps.max(1),
+    };
+    Some(hours * 3_600_000 + minutes * 60_000 + (seconds * 1000.0).round() as i64 + frames)
+}
+
+fn parse_start(cell: &Data) -> Option<PlanStart> {
+    match cell {
+        Data::DateTime(dt) => {
+            if dt.as_f64() < 1.0 {
+                excel_datetime(dt.as_f64()).map(|d| PlanStart::Time(d.time()))
+            } else {
+                excel_datetime(dt.as_f64()).map(PlanStart::DateTime)
+            }
+        }
+        Data::Float(f) if *f < 1.0 => excel_datetime(*f).map(|d| PlanStart::Time(d.time())),
+        Data::Float(f) => excel_datetime(*f).map(PlanStart::DateTime),
+        Data::DateTimeIso(s) | Data::String(s) => {
+            let s = s.trim();
+            for fmt in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%d.%m.%Y %H:%M:%S", "%d.%m.%Y %H:%M"] {
+                if let Ok(d) = NaiveDateTime::parse_from_str(s, fmt) {
+                    return Some(PlanStart::DateTime(d));
+                }
+            }
+            for fmt in ["%H:%M:%S", "%H:%M"] {
+                if let Ok(t) = NaiveTime::parse_from_str(s, fmt) {
+                    return Some(PlanStart::Time(t));
+                }
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+impl PlanStart {
+    // distance in milliseconds, a plain time is compared on the same day
+    pub fn distance(&self, time: NaiveDateTime) -> i64 {
+        match self {
+            PlanStart::DateTime(d) => (*d - time).num_milliseconds().abs(),
+            PlanStart::Time(t) => {
+                let day = 86_400_000;
+                let a = t.num_seconds_from_midnight() as i64 * 1000;
+                let b = time.time().num_seconds_from_midnight() as i64 * 1000;
+                let diff = (a - b).abs();
+                diff.min(day - diff)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PlanStart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanStart::DateTime(d) => write!(f, "{}", d.format("%d.%m.%Y %H:%M:%S")),
+            PlanStart::Time(t) => write!(f, "{}", t.format("%H:%M:%S")),
+        }
+    }
+}
+
+impl Plan {
+    pub fn load(path: &str, sheet: Option<&str>, fps: i64) -> Result<Plan, Box<dyn Error>> {
+        let mut workbook = open_workbook_auto(path)?;
+        let sheet = match sheet {
+            Some(sheet) => sheet.to_string(),
+            None => workbook
+                .sheet_names()
+                .first()
+                .cloned()
+                .ok_or(Msg::PlanNoSheets.text())?,
+        };
+        let range = workbook.worksheet_range(&sheet)?;
+        let first_row = range.start().map(|(row, _)| row as usize).unwrap_or(0);
+
+        let mut header = None;
+        let mut breaks: Vec<PlanBreak> = Vec::new();
+        let mut current: Option<PlanBreak> = None;
+
+        for (row_index, row) in range.rows().enumerate() {
+            let header = match &header {
+                None => {
+                    header = find_header(row);
+                    continue;
+                }
+                Some(header) => header,
+            };
+            let cell = |i: usize| row.get(i).cloned().unwrap_or(Data::Empty);
+            let title = cell(header.title).to_string().trim().to_string();
+
+            if title.is_empty() {
+                // without a block column empty lines separate the breaks
+                if header.block.is_none() {
+                    if let Some(b) = current.take() {
+                        breaks.push(b);
+                    }
+                }
+                continue;
+            }
+
+            let key = header
+                .block
+                .map(|i| cell(i).to_string().trim().to_string())
+                .filter(|k| !k.is_empty());
+            let start = header.start.and_then(|i| parse_start(&cell(i)));
+
+            let new_break = match (&current, &key) {
+                (None, _) => true,
+                (Some(b), Some(key)) => &b.key != key,
+                (Some(..), None) => false,
+            };
+            if new_break {
+                if let Some(b) = current.take() {
+                    breaks.push(b);
+                }
+                current = Some(PlanBreak {
+                    key: key.unwrap_or_else(|| format!("{}", breaks.len() + 1)),
+                    start: start.clone(),
+                    spots: Vec::new(),
+                });
+            }
+
+            let duration = match parse_duration(&cell(header.duration), fps) {
+                Some(duration) => duration,
+                None => {
+                    return Err(Msg::PlanInvalidDuration
+                        .fill(&[
+                            &(first_row + row_index + 1),
+                            &format!("{:?}", cell(header.duration).to_string()),
+                        ])
+                        .into())
+                }
+            };
+            if let Some(b) = current.as_mut() {
+                if b.start.is_none() {
+                    b.start = start;
+                }
+                b.spots.push(PlanSpot {
+                    title,
+                    duration,
+                    row: first_row + row_index + 1,
+                });
+            }
+        }
+        if let Some(b) = current.take() {
+            breaks.push(b);
+        }
+
+        if header.is_none() {
+            return Err(Msg::PlanNoHeader.fill(&[&format!("{:?}", sheet)]).into());
+        }
+        Ok(Plan { breaks })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Deviation {
+    Missing(usize),
+    Extra(usize),
+    Reordered(usize, usize),
+    Length(usize, usize),
+}
+
+pub fn normalize_title(title: &str) -> String {
+    title
+        .replace("UHD1_WERBUNG-01", "")
+        .trim()
+        .trim_start_matches('-')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+// longest common subsequence of the titles, everything outside of it was
+// either moved, dropped or added
+fn common_order(planned: &[String], scheduled: &[String]) -> Vec<(usize, usize)> {
+    let (n, m) = (planned.len(), scheduled.len());
+    let mut table = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if planned[i] == scheduled[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if planned[i] == scheduled[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+pub fn compare(planned: &[PlanSpot], scheduled: &[(String, i64)], tolerance: i64) -> Vec<Deviation> {
+    let planned_titles: Vec<String> = planned.iter().map(|s| normalize_title(&s.title)).collect();
+    let scheduled_titles: Vec<String> = scheduled.iter().map(|(t, _)| normalize_title(t)).collect();
+
+    let mut pairs = common_order(&planned_titles, &scheduled_titles);
+    let mut used_planned: Vec<bool> = vec![false; planned.len()];
+    let mut used_scheduled: Vec<bool> = vec![false; scheduled.len()];
+    for (i, j) in &pairs {
+        used_planned[*i] = true;
+        used_scheduled[*j] = true;
+    }
+
+    let mut deviations = Vec::new();
+    for i in 0..planned.len() {
+        if used_planned[i] {
+            continue;
+        }
+        let moved = (0..scheduled.len())
+            .find(|&j| !used_scheduled[j] && scheduled_titles[j] == planned_titles[i]);
+        match moved {
+            Some(j) => {
+                used_scheduled[j] = true;
+                deviations.push(Deviation::Reordered(i, j));
+                pairs.push((i, j));
+            }
+            None => deviations.push(Deviation::Missing(i)),
+        }
+    }
+    for (j, used) in used_scheduled.iter().enumerate() {
+        if !used {
+            deviations.push(Deviation::Extra(j));
+        }
+    }
+    for (i, j) in pairs {
+        if (planned[i].duration - scheduled[j].1).abs() > tolerance {
+            deviations.push(Deviation::Length(i, j));
+        }
+    }
+    deviations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spot(title: &str, duration: i64) -> PlanSpot {
+        PlanSpot {
+            title: title.to_string(),
+            duration,
+            row: 0,
+        }
+    }
+
+    fn scheduled(titles: &[(&str, i64)]) -> Vec<(String, i64)> {
+        titles.iter().map(|(t, d)| (t.to_string(), *d)).collect()
+    }
+
+    #[test]
+    fn durations_from_strings() {
+        assert_eq!(parse_duration_str("30", 25), Some(30_000));
+        assert_eq!(parse_duration_str("1:30", 25), Some(90_000));
+        assert_eq!(parse_duration_str("00:00:20,5", 25), Some(20_500));
+        assert_eq!(parse_duration_str("00:00:20:10", 25), Some(20_400));
+        assert_eq!(parse_duration_str("00:00:20:10", 50), Some(20_200));
+        assert_eq!(parse_duration_str("zwanzig", 25), None);
+    }
+
+    #[test]
+    fn small_numbers_are_excel_times() {
+        let twenty_seconds = 20.0 / 86_400.0;
+        assert_eq!(parse_duration(&Data::Float(twenty_seconds), 25), Some(20_000));
+        assert_eq!(parse_duration(&Data::Float(20.0), 25), Some(20_000));
+        assert_eq!(parse_duration(&Data::Int(15), 25), Some(15_000));
+    }
+
+    #[test]
+    fn titles_are_normalized() {
+        assert_eq!(normalize_title(" - Spot  A UHD1_WERBUNG-01"), "spot a");
+    }
+
+    #[test]
+    fn plain_times_are_compared_across_midnight() {
+        let start = PlanStart::Time(NaiveTime::from_hms_opt(23, 59, 0).unwrap());
+        let time = NaiveDate::from_ymd_opt(2024, 5, 2)
+            .unwrap()
+            .and_hms_opt(0, 1, 0)
+            .unwrap();
+        assert_eq!(start.distance(time), 120_000);
+    }
+
+    #[test]
+    fn compare_finds_every_deviation() {
+        let planned = vec![
+            spot("A", 30_000),
+            spot("B", 20_000),
+            spot("C", 10_000),
+            spot("D", 15_000),
+        ];
+        let actual = scheduled(&[("C", 10_000), ("A", 30_000), ("B", 25_000), ("E", 5_000)]);
+        let deviations = compare(&planned, &actual, 0);
+        assert!(deviations.contains(&Deviation::Missing(3)));
+        assert!(deviations.contains(&Deviation::Extra(3)));
+        assert!(deviations.contains(&Deviation::Reordered(2, 0)));
+        assert!(deviations.contains(&Deviation::Length(1, 2)));
+        assert_eq!(deviations.len(), 4);
+    }
+
+    #[test]
+    fn compare_accepts_the_plan() {
+        let planned = vec![spot("A", 30_000), spot("B", 20_000)];
+        let actual = scheduled(&[(" - A UHD1_WERBUNG-01", 30_040), ("b", 20_000)]);
+        assert!(compare(&planned, &actual, 40).is_empty());
+    }
+
+    #[test]
+    fn load_groups_spots_by_block() {
+        let path = std::env::temp_dir().join(format!("pts_plan_{}.xlsx", std::process::id()));
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        let sheet = workbook.add_worksheet();
+        let rows = [
+            ["Block", "Titel", "Dauer"],
+            ["1", "Spot A", "00:00:30"],
+            ["1", "Spot B", "20"],
+            ["2", "Spot C", "00:00:10:12"],
+        ];
+        for (r, row) in rows.iter().enumerate() {
+            for (c, value) in row.iter().enumerate() {
+                sheet.write_string(r as u32 + 1, c as u16, *value).unwrap();
+            }
+        }
+        workbook.save(&path).unwrap();
+
+        let plan = Plan::load(path.to_str().unwrap(), None, 25).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(plan.breaks.len(), 2);
+        assert_eq!(plan.breaks[0].key, "1");
+        assert_eq!(plan.breaks[0].spots[1].duration, 20_000);
+        assert_eq!(plan.breaks[0].spots[1].row, 4);
+        assert_eq!(plan.breaks[1].spots[0].duration, 10_480);
+    }
+}
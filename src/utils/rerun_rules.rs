@@ -0,0 +1,61 @@
+use serde::Deserialize;
+
+pub const DEFAULT_RULES: &str = include_str!("rerun_rules.json");
+
+// How often content may be repeated. The first rule whose classes or content
+// ids match applies, a rule without either applies to everything.
+#[derive(Deserialize)]
+pub struct RerunRule {
+    pub name: String,
+    #[serde(default)]
+    pub classes: Vec<String>,
+    #[serde(default)]
+    pub content_ids: Vec<String>,
+    // minutes from the end of one airing to the start of the next
+    #[serde(default)]
+    pub min_gap: i64,
+    pub max_per_day: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct RerunRules {
+    // content ids (or parts of them) that are not counted at all
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    pub rules: Vec<RerunRule>,
+}
+
+impl RerunRule {
+    fn applies(&self, content_id: &str, class: Option<&str>) -> bool {
+        if self.classes.is_empty() && self.content_ids.is_empty() {
+            return true;
+        }
+        self.content_ids
+            .iter()
+            .any(|x| !x.is_empty() && content_id.contains(x.as_str()))
+            || class.is_some_and(|class| {
+                self.classes
+                    .iter()
+                    .any(|x| x.trim().eq_ignore_ascii_case(class.trim()))
+            })
+    }
+}
+
+impl RerunRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("rerun_rules.json is valid")
+    }
+
+    pub fn ignored(&self, content_id: &str) -> bool {
+        self.ignore
+            .iter()
+            .any(|x| !x.is_empty() && content_id.contains(x.as_str()))
+    }
+
+    pub fn rule(&self, content_id: &str, class: Option<&str>) -> Option<&RerunRule> {
+        self.rules
+            .iter()
+            .find(|rule| rule.applies(content_id, class))
+    }
+}
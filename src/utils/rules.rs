@@ -0,0 +1,36 @@
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::fs;
+
+// The rules of a check: the json file given on the command line or the one
+// built into the binary.
+pub fn load_rules<T: DeserializeOwned>(
+    path: Option<&str>,
+    default: &str,
+) -> Result<T, Box<dyn Error>> {
+    match path {
+        None => Ok(serde_json::from_str(default)?),
+        Some(path) => Ok(serde_json::from_str(&fs::read_to_string(path)?)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::block_rules::{self, BlockRules};
+
+    #[test]
+    fn built_in_or_file_rules() {
+        let rules: BlockRules = load_rules(None, block_rules::DEFAULT_RULES).unwrap();
+        assert!(rules.is_begin("3ecea6b8084f35c2634d"));
+
+        let path = std::env::temp_dir().join("pts_block_rules.json");
+        fs::write(&path, r#"{"begin": ["B"], "end": ["E"]}"#).unwrap();
+        let rules: BlockRules = load_rules(path.to_str(), block_rules::DEFAULT_RULES).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(rules.is_begin("B") && !rules.is_begin("3ecea6b8084f35c2634d"));
+
+        let missing = load_rules::<BlockRules>(Some("missing.json"), block_rules::DEFAULT_RULES);
+        assert!(missing.is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::pts_loader::event::Event;
+use serde::Deserialize;
+
+pub const DEFAULT_RULES: &str = include_str!("sendepause_rules.json");
+
+// What a Sendepause is, which material may fill it and what must not be on
+// air during it.
+#[derive(Deserialize)]
+pub struct SendepauseRules {
+    // parts of siEvent titles
+    pub titles: Vec<String>,
+    // parts of vaEvent titles or content ids of the loop material
+    #[serde(default)]
+    pub loop_titles: Vec<String>,
+    #[serde(default)]
+    pub loop_content_ids: Vec<String>,
+    // minutes a loop may run in one piece
+    pub max_loop: Option<i64>,
+    // event types, e.g. "logoEvent"
+    #[serde(default)]
+    pub forbidden: Vec<String>,
+}
+
+fn contains_any(text: &str, parts: &[String]) -> bool {
+    parts
+        .iter()
+        .any(|part| !part.is_empty() && text.contains(part.as_str()))
+}
+
+impl SendepauseRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("sendepause_rules.json is valid")
+    }
+
+    pub fn is_sendepause(&self, event: &Event) -> bool {
+        contains_any(&event.get_title(), &self.titles)
+    }
+
+    pub fn is_loop(&self, event: &Event) -> bool {
+        contains_any(&event.get_title(), &self.loop_titles)
+            || contains_any(&event.get_contentid(), &self.loop_content_ids)
+    }
+
+    pub fn is_forbidden(&self, kind: &str) -> bool {
+        self.forbidden.iter().any(|x| x == kind)
+    }
+}
@@ -0,0 +1,223 @@
+use crate::utils::glob;
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use zip::ZipArchive;
+
+pub const STDIN: &str = "-";
+
+const GZIP_MAGIC: &[u8] = b"\x1f\x8b";
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+pub struct Source {
+    // what is shown to the user, e.g. "woche.zip:montag.pts"
+    pub name: String,
+    // the file name inside of an archive or without the .gz
+    pub entry: Option<String>,
+    pub bytes: Vec<u8>,
+}
+
+fn gunzip(bytes: &[u8]) -> io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    GzDecoder::new(bytes).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+pub fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn directory(path: &str) -> &str {
+    &path[..path.len() - file_name(path).len()]
+}
+
+// a file in the same directory as `path`
+pub fn beside(path: &str, name: &str) -> String {
+    format!("{}{}", directory(path), file_name(name))
+}
+
+fn strip_gz(name: &str) -> &str {
+    match name.len().checked_sub(3) {
+        Some(i) if name.is_char_boundary(i) && name[i..].eq_ignore_ascii_case(".gz") => &name[..i],
+        _ => name,
+    }
+}
+
+fn selected(name: &str, patterns: &[&str]) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| glob::matches(pattern, name) || glob::matches(pattern, file_name(name)))
+}
+
+fn is_schedule(name: &str) -> bool {
+    let name = strip_gz(name).to_lowercase();
+    name.ends_with(".pts") || name.ends_with(".xml")
+}
+
+// without a selection every .pts and .xml file of the archive is loaded
+fn unzip(path: &str, bytes: Vec<u8>, entries: Option<&str>) -> io::Result<Vec<Source>> {
+    let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+    let patterns: Vec<&str> = entries
+        .map(|e| {
+            e.split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut sources = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        let name = file.name().to_string();
+        if file.is_dir() || name.starts_with("__MACOSX/") {
+            continue;
+        }
+        let wanted = if patterns.is_empty() {
+            is_schedule(&name)
+        } else {
+            selected(&name, &patterns)
+        };
+        if !wanted {
+            continue;
+        }
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        if data.starts_with(GZIP_MAGIC) {
+            data = gunzip(&data)?;
+        }
+        sources.push(Source {
+            name: format!("{}:{}", path, strip_gz(&name)),
+            entry: Some(strip_gz(&name).to_string()),
+            bytes: data,
+        });
+    }
+    Ok(sources)
+}
+
+// reads a plain file, a .gz file, the entries of a .zip archive or stdin for "-"
+pub fn open(path: &str, entries: Option<&str>) -> io::Result<Vec<Source>> {
+    let bytes = if path == STDIN {
+        let mut data = Vec::new();
+        io::stdin().read_to_end(&mut data)?;
+        data
+    } else {
+        fs::read(path)?
+    };
+
+    if bytes.starts_with(ZIP_MAGIC) {
+        return unzip(path, bytes, entries);
+    }
+    if bytes.starts_with(GZIP_MAGIC) {
+        return Ok(vec![Source {
+            name: path.to_string(),
+            entry: Some(file_name(strip_gz(path)).to_string()),
+            bytes: gunzip(&bytes)?,
+        }]);
+    }
+    Ok(vec![Source {
+        name: path.to_string(),
+        entry: None,
+        bytes,
+    }])
+}
+
+// "bloecke.csv" becomes "bloecke_montag.csv" for the entry "montag.pts"
+pub fn entry_path(path: &str, entry: Option<&str>) -> String {
+    let Some(entry) = entry else {
+        return path.to_string();
+    };
+    let entry = file_name(entry);
+    let stem = entry
+        .rsplit_once('.')
+        .map(|(stem, _)| stem)
+        .unwrap_or(entry);
+    let directory = directory(path);
+    match file_name(path).rsplit_once('.') {
+        Some((base, extension)) if !base.is_empty() => {
+            format!("{}{}_{}.{}", directory, base, stem, extension)
+        }
+        _ => format!("{}_{}", path, stem),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn temp_file(name: &str, bytes: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("pts_{}_{}", std::process::id(), name));
+        fs::write(&path, bytes).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(selected("woche/montag.pts", &["montag.pts"]));
+        assert!(selected("woche/montag.pts", &["*.pts"]));
+        assert!(!selected("woche/montag.xml", &["mo*.pts"]));
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(strip_gz("montag.pts.GZ"), "montag.pts");
+        assert_eq!(strip_gz("gz"), "gz");
+        assert_eq!(
+            beside("C:\\pts\\woche.zip", "tage/montag.pts"),
+            "C:\\pts\\montag.pts"
+        );
+        assert_eq!(
+            entry_path("out/bloecke.csv", Some("tage/montag.pts")),
+            "out/bloecke_montag.csv"
+        );
+        assert_eq!(entry_path("bloecke", Some("montag.pts")), "bloecke_montag");
+        assert_eq!(entry_path("bloecke.csv", None), "bloecke.csv");
+    }
+
+    #[test]
+    fn gzip_files_are_unpacked() {
+        let path = temp_file("montag.pts.gz", &gzip(b"<PTS/>"));
+        let sources = open(&path, None).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].bytes, b"<PTS/>");
+        assert!(sources[0].entry.as_deref().unwrap().ends_with("montag.pts"));
+    }
+
+    #[test]
+    fn zip_archives_load_the_selected_schedules() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let files: [(&str, Vec<u8>); 4] = [
+            ("montag.pts", b"<PTS>1</PTS>".to_vec()),
+            ("dienstag.pts.gz", gzip(b"<PTS>2</PTS>")),
+            ("liesmich.txt", b"text".to_vec()),
+            ("__MACOSX/montag.pts", b"junk".to_vec()),
+        ];
+        for (name, data) in &files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(data).unwrap();
+        }
+        let path = temp_file("woche.zip", &writer.finish().unwrap().into_inner());
+
+        let all = open(&path, None).unwrap();
+        let some = open(&path, Some("dien*")).unwrap();
+        fs::remove_file(&path).unwrap();
+        let entries: Vec<_> = all.iter().map(|s| s.entry.clone().unwrap()).collect();
+        assert_eq!(entries, ["montag.pts", "dienstag.pts"]);
+        assert_eq!(all[1].bytes, b"<PTS>2</PTS>");
+        assert_eq!(all[1].name, format!("{}:dienstag.pts", path));
+        assert_eq!(some.len(), 1);
+    }
+}
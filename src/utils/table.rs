@@ -0,0 +1,388 @@
+use crate::commandline::commandline::Commandline;
+use crate::utils::messages::Msg;
+use colored::{ColoredString, Colorize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const SEPARATOR_WIDTH: usize = 3;
+
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+// cut a string to at most `width` terminal columns and pad it with spaces,
+// so wide characters (emojis, asian scripts) and accents keep the table aligned
+pub fn fit(s: &str, width: usize) -> String {
+    let mut result = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = UnicodeWidthChar::width(c).unwrap_or(0);
+        if used + w > width {
+            break;
+        }
+        used += w;
+        result.push(c);
+    }
+    result + &" ".repeat(width - used)
+}
+
+pub fn to_ascii(s: &str) -> String {
+    let mut result = String::new();
+    for c in s.chars() {
+        if c.is_ascii() {
+            result.push(c);
+            continue;
+        }
+        result += match c {
+            'ä' => "ae",
+            'ö' => "oe",
+            'ü' => "ue",
+            'Ä' => "Ae",
+            'Ö' => "Oe",
+            'Ü' => "Ue",
+            'ß' => "ss",
+            'à' | 'á' | 'â' | 'ã' | 'å' => "a",
+            'À' | 'Á' | 'Â' | 'Ã' | 'Å' => "A",
+            'è' | 'é' | 'ê' | 'ë' => "e",
+            'È' | 'É' | 'Ê' | 'Ë' => "E",
+            'ì' | 'í' | 'î' | 'ï' => "i",
+            'Ì' | 'Í' | 'Î' | 'Ï' => "I",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ø' => "o",
+            'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ø' => "O",
+            'ù' | 'ú' | 'û' => "u",
+            'Ù' | 'Ú' | 'Û' => "U",
+            'ç' => "c",
+            'Ç' => "C",
+            'ñ' => "n",
+            'Ñ' => "N",
+            '–' | '—' => "-",
+            '‘' | '’' | '´' | '`' => "'",
+            '“' | '”' | '„' => "\"",
+            _ => {
+                if UnicodeWidthChar::width(c).unwrap_or(0) == 0 {
+                    ""
+                } else {
+                    "?"
+                }
+            }
+        };
+    }
+    result
+}
+
+pub fn terminal_width(cmd: &Commandline) -> Option<usize> {
+    if let Some(width) = cmd.width() {
+        return Some(width);
+    }
+    if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+        return Some(width as usize);
+    }
+    std::env::var("COLUMNS").ok()?.trim().parse().ok()
+}
+
+#[derive(Clone)]
+pub struct Column {
+    key: &'static str,
+    header: String,
+    max_width: usize,
+    min_width: usize,
+}
+
+impl Column {
+    pub fn new(key: &'static str, header: &str, max_width: usize) -> Self {
+        Column {
+            key,
+            header: header.to_string(),
+            max_width,
+            min_width: display_width(header).max(4).min(max_width),
+        }
+    }
+}
+
+enum Row {
+    Cells(Vec<ColoredString>),
+    Marker(String),
+    Separator,
+}
+
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+    selection: Option<Vec<usize>>,
+    header_every: Option<usize>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Self {
+        Table {
+            columns,
+            rows: Vec::new(),
+            selection: None,
+            header_every: None,
+        }
+    }
+
+    // show only the given columns in the given order
+    pub fn select(&mut self, keys: &[String]) {
+        let mut selection = Vec::new();
+        for key in keys {
+            match self.columns.iter().position(|c| c.key == key.trim()) {
+                Some(i) => selection.push(i),
+                None => println!(
+                    "{}",
+                    Msg::UnknownColumn.fill(&[
+                        &format!("{:?}", key),
+                        &self
+                            .columns
+                            .iter()
+                            .map(|c| c.key)
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    ])
+                ),
+            }
+        }
+        if !selection.is_empty() {
+            self.selection = Some(selection);
+        }
+    }
+
+    pub fn repeat_header(&mut self, every: usize) {
+        self.header_every = Some(every);
+    }
+
+    pub fn add_row(&mut self, cells: Vec<ColoredString>) {
+        self.rows.push(Row::Cells(cells));
+    }
+
+    pub fn add_marker(&mut self, label: &str) {
+        self.rows.push(Row::Marker(label.to_string()));
+    }
+
+    pub fn add_separator(&mut self) {
+        if let Some(Row::Cells(..)) = self.rows.last() {
+            self.rows.push(Row::Separator);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.rows.iter().any(|row| matches!(row, Row::Cells(..)))
+    }
+
+    fn visible(&self) -> Vec<usize> {
+        match &self.selection {
+            Some(selection) => selection.clone(),
+            None => (0..self.columns.len()).collect(),
+        }
+    }
+
+    fn widths(&self, visible: &[usize], ascii: bool, available: Option<usize>) -> Vec<usize> {
+        let mut widths: Vec<usize> = visible
+            .iter()
+            .enumerate()
+            .map(|(n, &i)| {
+                let column = &self.columns[i];
+                let last = n + 1 == visible.len();
+                let content = self
+                    .rows
+                    .iter()
+                    .map(|row| match row {
+                        Row::Cells(cells) => cells
+                            .get(i)
+                            .map(|cell| cell_width(&cell.input, ascii))
+                            .unwrap_or(0),
+                        // the marker label is printed in the last column
+                        Row::Marker(label) if last => cell_width(label, ascii),
+                        _ => 0,
+                    })
+                    .max()
+                    .unwrap_or(0);
+                content
+                    .max(display_width(&column.header))
+                    .min(column.max_width)
+                    .max(column.min_width)
+            })
+            .collect();
+
+        if let Some(available) = available {
+            let border = SEPARATOR_WIDTH * widths.len() + 1;
+            // shrink the widest column until the table fits or nothing can shrink anymore
+            while widths.iter().sum::<usize>() + border > available {
+                let widest = visible
+                    .iter()
+                    .enumerate()
+                    .filter(|(n, &i)| widths[*n] > self.columns[i].min_width)
+                    .max_by_key(|(n, _)| widths[*n])
+                    .map(|(n, _)| n);
+                match widest {
+                    Some(n) => widths[n] -= 1,
+                    None => break,
+                }
+            }
+        }
+        widths
+    }
+
+    fn line(widths: &[usize], cross: char) -> String {
+        let parts: Vec<String> = widths.iter().map(|w| "-".repeat(w + 2)).collect();
+        format!("|{}|", parts.join(&cross.to_string()))
+    }
+
+    fn render_cells(cells: &[ColoredString], widths: &[usize], ascii: bool) -> String {
+        let parts: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| {
+                let text = if ascii {
+                    to_ascii(&cell.input)
+                } else {
+                    cell.input.clone()
+                };
+                let mut cell = cell.clone();
+                cell.input = fit(&text, *width);
+                format!("{}", cell)
+            })
+            .collect();
+        format!("| {} |", parts.join(" | "))
+    }
+
+    pub fn print(&self, cmd: &Commandline) {
+        for line in self.render(cmd) {
+            println!("{}", line);
+        }
+    }
+
+    pub fn render(&self, cmd: &Commandline) -> Vec<String> {
+        let ascii = cmd.ascii();
+        let visible = self.visible();
+        let widths = self.widths(&visible, ascii, terminal_width(cmd));
+        let pick = |cells: &Vec<ColoredString>| -> Vec<ColoredString> {
+            visible
+                .iter()
+                .map(|&i| cells.get(i).cloned().unwrap_or_else(|| "".clear()))
+                .collect()
+        };
+        let header_cells: Vec<ColoredString> = self
+            .columns
+            .iter()
+            .map(|c| c.header.as_str().blue())
+            .collect();
+        let header = Table::render_cells(&pick(&header_cells), &widths, ascii);
+        let cross = Table::line(&widths, '+');
+
+        let mut lines = vec![Table::line(&widths, '-'), header.clone(), cross.clone()];
+        let mut since_header = 0;
+        for row in &self.rows {
+            match row {
+                Row::Cells(cells) => {
+                    lines.push(Table::render_cells(&pick(cells), &widths, ascii));
+                    since_header += 1;
+                    if let Some(every) = self.header_every {
+                        if since_header % every == 0 {
+                            lines.push(cross.clone());
+                            lines.push(header.clone());
+                            lines.push(cross.clone());
+                        }
+                    }
+                }
+                Row::Marker(label) => {
+                    let mut cells: Vec<ColoredString> = widths
+                        .iter()
+                        .map(|w| "-".repeat(*w).black().on_red())
+                        .collect();
+                    if let Some(last) = cells.last_mut() {
+                        *last = label.as_str().black().on_red();
+                    }
+                    lines.push(Table::render_cells(&cells, &widths, ascii));
+                }
+                Row::Separator => {
+                    if lines.last() != Some(&cross) {
+                        lines.push(cross.clone());
+                    }
+                }
+            }
+        }
+        if lines.last() != Some(&cross) {
+            lines.push(cross);
+        }
+        lines.push(header);
+        lines.push(Table::line(&widths, '-'));
+        lines
+    }
+}
+
+fn cell_width(s: &str, ascii: bool) -> usize {
+    let s = s.trim_end();
+    if ascii {
+        to_ascii(s).len()
+    } else {
+        display_width(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(table: &Table, args: &[&str]) -> Vec<String> {
+        colored::control::set_override(false);
+        table.render(&Commandline::from_args(args))
+    }
+
+    #[test]
+    fn fit_counts_display_columns() {
+        assert_eq!(fit("🎬ab", 4), "🎬ab");
+        assert_eq!(fit("🎬ab", 3), "🎬a");
+        assert_eq!(fit("Été", 5), "Été  ");
+    }
+
+    #[test]
+    fn to_ascii_replaces_umlauts_and_emojis() {
+        assert_eq!(to_ascii("Über Été 🎬"), "Ueber Ete ?");
+    }
+
+    #[test]
+    fn min_width_uses_display_width() {
+        let column = Column::new("overlap", "Überlappung", 20);
+        assert_eq!(column.min_width, 11);
+        let column = Column::new("x", "🎬", 20);
+        assert_eq!(column.min_width, 4);
+    }
+
+    #[test]
+    fn columns_are_sized_by_content() {
+        let mut table = Table::new(vec![
+            Column::new("a", "A", 10),
+            Column::new("b", "B", 10),
+        ]);
+        table.add_row(vec!["Été".normal(), "🎬🎬🎬".normal()]);
+        let lines = render(&table, &["--width", "200"]);
+        assert_eq!(lines[1], "| A    | B      |");
+        assert_eq!(lines[3], "| Été  | 🎬🎬🎬 |");
+    }
+
+    #[test]
+    fn select_reorders_columns() {
+        let mut table = Table::new(vec![
+            Column::new("a", "A", 10),
+            Column::new("b", "B", 10),
+            Column::new("c", "C", 10),
+        ]);
+        table.add_row(vec!["1".normal(), "2".normal(), "3".normal()]);
+        table.select(&["c".to_string(), " a".to_string(), "x".to_string()]);
+        let lines = render(&table, &["--width", "200"]);
+        assert_eq!(lines[1], "| C    | A    |");
+        assert_eq!(lines[3], "| 3    | 1    |");
+    }
+
+    #[test]
+    fn widest_column_shrinks_to_the_width() {
+        let mut table = Table::new(vec![
+            Column::new("a", "A", 30),
+            Column::new("b", "B", 30),
+        ]);
+        table.add_row(vec!["x".repeat(30).normal(), "y".repeat(10).normal()]);
+        let lines = render(&table, &["--width", "30"]);
+        assert!(lines.iter().all(|line| display_width(line) <= 30));
+        assert_eq!(lines[3], format!("| {} | {} |", "x".repeat(13), "y".repeat(10)));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::pts_loader::event::Event;
+use crate::utils::fluid::{Fluid, QueryType};
+use chrono::NaiveTime;
+use csv::ReaderBuilder;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+
+pub const DEFAULT_RULES: &str = include_str!("youth_rules.json");
+
+// material rated `age` or older may only air from `from` until `until`
+#[derive(Deserialize)]
+pub struct AirtimeWindow {
+    pub age: u32,
+    pub from: NaiveTime,
+    pub until: NaiveTime,
+}
+
+#[derive(Deserialize)]
+pub struct YouthRules {
+    // fluid columns holding the age rating, the first one with a number wins
+    #[serde(default)]
+    pub columns: Vec<String>,
+    pub windows: Vec<AirtimeWindow>,
+    // content id -> rating, from --ratings
+    #[serde(skip)]
+    ratings: HashMap<String, String>,
+}
+
+// "FSK 16", "ab 16" and "16" are all 16
+fn age(rating: &str) -> Option<u32> {
+    let digits: String = rating
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+impl YouthRules {
+    #[cfg(test)]
+    pub fn default_rules() -> Self {
+        super::rules::load_rules(None, DEFAULT_RULES).expect("youth_rules.json is valid")
+    }
+
+    // a csv file with the columns ContentId and Rating
+    pub fn load_ratings(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        let text = fs::read_to_string(path)?;
+        let delimiter = [b';', b',', b'\t']
+            .into_iter()
+            .find(|d| text.lines().next().unwrap_or("").contains(*d as char))
+            .unwrap_or(b';');
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .from_reader(text.as_bytes());
+        for record in reader.deserialize() {
+            let record: HashMap<String, String> = record?;
+            if let (Some(id), Some(rating)) = (record.get("ContentId"), record.get("Rating")) {
+                self.ratings
+                    .insert(id.trim().to_string(), rating.trim().to_string());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn rating(&self, event: &Event, fluid: &Fluid) -> Option<u32> {
+        if let Some(rating) = self.ratings.get(event.get_contentid().as_str()) {
+            return age(rating);
+        }
+        self.columns
+            .iter()
+            .filter_map(|column| fluid.query(event, QueryType::Column(column.clone())))
+            .find_map(|rating| age(&rating))
+    }
+
+    // the strictest window that applies to `age`
+    pub fn window(&self, age: u32) -> Option<&AirtimeWindow> {
+        self.windows
+            .iter()
+            .filter(|window| window.age <= age)
+            .max_by_key(|window| window.age)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ages_of_ratings() {
+        assert_eq!(age("FSK 16"), Some(16));
+        assert_eq!(age("ab 18 Jahren"), Some(18));
+        assert_eq!(age("12"), Some(12));
+        assert_eq!(age("ohne Altersbeschränkung"), None);
+    }
+
+    #[test]
+    fn strictest_window_for_an_age() {
+        let rules = YouthRules::default_rules();
+        let from = |age| rules.window(age).map(|window| window.age);
+        assert_eq!(from(12), None);
+        assert_eq!(from(16), Some(16));
+        assert_eq!(from(17), Some(16));
+        assert_eq!(from(18), Some(18));
+    }
+
+    #[test]
+    fn ratings_file_before_fluid() {
+        let path = std::env::temp_dir().join("pts_youth_ratings.csv");
+        fs::write(&path, "ContentId,Rating\n 1572515-971182 ,FSK 18\n").unwrap();
+        let mut rules = YouthRules::default_rules();
+        rules.load_ratings(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut pts = crate::pts_loader::fixtures::Pts::new();
+        pts.va("Film", "1572515-971182", "18:00:00", 1000, "P100")
+            .va("Serie", "1600000-100001", "18:00:01", 1000, "P200");
+        let defines = pts.defines();
+        let fluid = Fluid::from_entries(&[
+            &[("ContentId", "1572515-971182"), ("FSK", "12")],
+            &[("ContentId", "1600000-100001"), ("AgeRating", "ab 16")],
+        ]);
+        assert_eq!(rules.rating(defines[0].get_event(), &fluid), Some(18));
+        assert_eq!(rules.rating(defines[1].get_event(), &fluid), Some(16));
+    }
+}